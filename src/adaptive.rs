@@ -0,0 +1,159 @@
+use crate::{AsyncRecvMsg, MsgBuf, RecvMsg, RecvResult};
+use core::{
+    cmp::min,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+const BUCKETS: usize = usize::BITS as usize;
+/// Number of recorded messages after which the history is halved.
+const DECAY_INTERVAL: u32 = 64;
+/// The estimate covers all but this fraction of the recorded messages.
+const MISS_FRACTION: u32 = 64;
+/// Owned buffers are only shrunk when they are at least this many times larger than the estimate,
+/// so as not to reallocate back and forth.
+const SHRINK_FACTOR: usize = 4;
+
+/// Tracks the sizes of recently received messages to predict how large a buffer the next one will
+/// need.
+///
+/// Message sizes are recorded in a histogram with power-of-two buckets whose counts are halved
+/// periodically, so that old traffic gradually stops mattering. The estimate is the smallest power
+/// of two that would have fit all but a small fraction of the recorded messages.
+#[derive(Clone, Debug)]
+pub struct SizeEstimator {
+    /// Bucket 𝑖 counts messages that fit into 2ⁱ bytes but not into 2ⁱ⁻¹.
+    counts: [u32; BUCKETS],
+    total: u32,
+    since_decay: u32,
+}
+impl Default for SizeEstimator {
+    #[inline]
+    fn default() -> Self {
+        Self { counts: [0; BUCKETS], total: 0, since_decay: 0 }
+    }
+}
+impl SizeEstimator {
+    /// Creates an estimator with no history.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the size of a received message.
+    pub fn record(&mut self, len: usize) {
+        let bucket = if len <= 1 { 0 } else { (usize::BITS - (len - 1).leading_zeros()) as usize };
+        self.counts[min(bucket, BUCKETS - 1)] += 1;
+        self.total += 1;
+        self.since_decay += 1;
+        if self.since_decay >= DECAY_INTERVAL {
+            self.since_decay = 0;
+            self.total = 0;
+            for count in &mut self.counts {
+                *count /= 2;
+                self.total += *count;
+            }
+        }
+    }
+
+    /// Returns the buffer size that the next message is expected to fit into, or zero if there is
+    /// no history to go by.
+    pub fn estimate(&self) -> usize {
+        let mut left = self.total / MISS_FRACTION;
+        for (bucket, &count) in self.counts.iter().enumerate().rev() {
+            if count > left {
+                return 1 << bucket;
+            }
+            left -= count;
+        }
+        0
+    }
+
+    /// Grows `buf` to the estimate if it's smaller, or replaces its owned allocation with one the
+    /// size of the estimate if it's several times larger. The contents of the buffer are discarded
+    /// if it is resized.
+    ///
+    /// The quota of the buffer is respected, and failure to allocate is ignored, leaving the
+    /// reception itself to deal with it.
+    pub fn presize(&self, buf: &mut MsgBuf<'_>) {
+        let estimate = self.estimate();
+        if estimate == 0 {
+            return;
+        }
+        let target = buf.quota.map_or(estimate, |quota| min(quota, estimate));
+        let cap = buf.capacity();
+        if cap < target {
+            let _ = buf.try_clear_and_grow_to(target);
+        } else if cap / SHRINK_FACTOR > target && buf.take_owned().is_some() {
+            let _ = buf.try_grow_to(target);
+        }
+    }
+
+    /// Records the size of the message received with the given result, if one was received.
+    fn record_result(&mut self, rslt: &RecvResult, buf: &MsgBuf<'_>) {
+        match *rslt {
+            RecvResult::Fit | RecvResult::Spilled => self.record(buf.len_filled()),
+            RecvResult::Truncated { full_len } => {
+                self.record(full_len.unwrap_or_else(|| buf.len_filled()))
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Adapter that pre-sizes the buffer before every reception based on the sizes of the messages
+/// previously received from the wrapped receiver, using a [`SizeEstimator`].
+///
+/// This makes it rare for the peek-and-grow loop of receivers built on
+/// [`recv_via_recv_trunc()`](crate::sync::recv_via_recv_trunc) to go around more than once,
+/// and keeps buffers that once received a huge message from holding on to their allocation
+/// forever.
+#[derive(Clone, Debug, Default)]
+pub struct WithAdaptiveSize<T> {
+    /// The underlying receiver.
+    pub inner: T,
+    /// The history of message sizes.
+    pub estimator: SizeEstimator,
+}
+impl<T> WithAdaptiveSize<T> {
+    /// Wraps the given receiver.
+    #[inline]
+    pub fn new(inner: T) -> Self {
+        Self { inner, estimator: SizeEstimator::new() }
+    }
+}
+
+impl<T: RecvMsg> RecvMsg for WithAdaptiveSize<T> {
+    type Error = T::Error;
+    type AddrBuf = T::AddrBuf;
+    fn recv_msg(
+        &mut self,
+        buf: &mut MsgBuf<'_>,
+        abuf: Option<&mut T::AddrBuf>,
+    ) -> Result<RecvResult, Self::Error> {
+        self.estimator.presize(buf);
+        let rslt = self.inner.recv_msg(buf, abuf)?;
+        self.estimator.record_result(&rslt, buf);
+        Ok(rslt)
+    }
+}
+
+impl<T: AsyncRecvMsg + Unpin> AsyncRecvMsg for WithAdaptiveSize<T> {
+    type Error = T::Error;
+    type AddrBuf = T::AddrBuf;
+    fn poll_recv_msg(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut MsgBuf<'_>,
+        abuf: Option<&mut T::AddrBuf>,
+    ) -> Poll<Result<RecvResult, Self::Error>> {
+        let Self { inner, estimator } = self.get_mut();
+        // Idempotent, so it doesn't matter that this happens again after `Poll::Pending`
+        estimator.presize(buf);
+        let rslt = Pin::new(inner).poll_recv_msg(cx, buf, abuf);
+        if let Poll::Ready(Ok(rslt)) = &rslt {
+            estimator.record_result(rslt, buf);
+        }
+        rslt
+    }
+}
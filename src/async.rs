@@ -40,7 +40,7 @@ mod r#impl {
     }
 }
 
-use crate::{MsgBuf, RecvResult, TryRecvResult};
+use crate::{MsgBatch, MsgBuf, RecvResult, TryRecvResult};
 use core::{
     pin::Pin,
     task::{Context, Poll},
@@ -159,6 +159,33 @@ fn _assert_object_safe_trmwfs<
     x
 }
 
+/// Like [`TruncatingRecvMsg`], but scatters each message across multiple buffers.
+///
+/// This is the async version of
+/// [`sync::TruncatingRecvMsgVectored`](super::TruncatingRecvMsgVectored).
+pub trait TruncatingRecvMsgVectored: TruncatingRecvMsg {
+    /// Like [`.poll_recv_trunc()`](TruncatingRecvMsg::poll_recv_trunc), but receives one message
+    /// into the sequence of buffers given by `bufs`, filling each one to capacity before moving on
+    /// to the next.
+    ///
+    /// See [`sync::TruncatingRecvMsgVectored::recv_trunc_vectored()`] for contract notes.
+    ///
+    /// [`sync::TruncatingRecvMsgVectored::recv_trunc_vectored()`]:
+    ///     super::TruncatingRecvMsgVectored::recv_trunc_vectored
+    fn poll_recv_trunc_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        peek: bool,
+        bufs: &mut [MsgBuf<'_>],
+        abuf: Option<&mut Self::AddrBuf>,
+    ) -> Poll<Result<Option<bool>, Self::Error>>;
+}
+fn _assert_object_safe_trmv<E, AB, TRMV: TruncatingRecvMsgVectored<Error = E, AddrBuf = AB>>(
+    x: &TRMV,
+) -> &(dyn TruncatingRecvMsgVectored<Error = E, AddrBuf = AB> + '_) {
+    x
+}
+
 /// Implementation of asynchronously receiving from socket-like connections with message boundaries
 /// reliably, without truncation.
 ///
@@ -194,3 +221,47 @@ fn _assert_object_safe_rm<E, AB, RM: RecvMsg<Error = E, AddrBuf = AB>>(
 ) -> &(dyn RecvMsg<Error = E, AddrBuf = AB> + '_) {
     x
 }
+
+/// Like [`RecvMsg`], but scatters each message across multiple buffers.
+///
+/// This is the async version of [`sync::RecvMsgVectored`](super::RecvMsgVectored).
+pub trait RecvMsgVectored: RecvMsg {
+    /// Polls a future that receives one message into the sequence of buffers given by `bufs`,
+    /// (re)allocating the last buffer if necessary.
+    ///
+    /// See [`sync::RecvMsgVectored::recv_msg_vectored()`](super::RecvMsgVectored::recv_msg_vectored)
+    /// for details.
+    fn poll_recv_msg_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [MsgBuf<'_>],
+        abuf: Option<&mut Self::AddrBuf>,
+    ) -> Poll<Result<RecvResult, Self::Error>>;
+}
+fn _assert_object_safe_rmv<E, AB, RMV: RecvMsgVectored<Error = E, AddrBuf = AB>>(
+    x: &RMV,
+) -> &(dyn RecvMsgVectored<Error = E, AddrBuf = AB> + '_) {
+    x
+}
+
+/// Receiving multiple messages in one call from socket-like connections with message boundaries,
+/// without truncation.
+///
+/// This is the async version of [`sync::RecvMsgBatch`](super::RecvMsgBatch).
+pub trait RecvMsgBatch: RecvMsg {
+    /// Polls a future that receives one or more messages into the buffers of `batch`,
+    /// (re)allocating them if necessary.
+    ///
+    /// See [`sync::RecvMsgBatch::recv_msg_batch()`](super::RecvMsgBatch::recv_msg_batch) for
+    /// details.
+    fn poll_recv_msg_batch(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        batch: &mut MsgBatch<'_, Self::AddrBuf>,
+    ) -> Poll<Result<usize, Self::Error>>;
+}
+fn _assert_object_safe_rmb<E, AB, RMB: RecvMsgBatch<Error = E, AddrBuf = AB>>(
+    x: &RMB,
+) -> &(dyn RecvMsgBatch<Error = E, AddrBuf = AB> + '_) {
+    x
+}
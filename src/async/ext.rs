@@ -71,6 +71,29 @@ pub trait TruncatingRecvMsgWithFullSizeExt: TruncatingRecvMsgWithFullSize {
 }
 impl<T: TruncatingRecvMsgWithFullSize + ?Sized> TruncatingRecvMsgWithFullSizeExt for T {}
 
+/// Futures for reception from socket-like connections with message boundaries with truncation
+/// detection, scattering each message across multiple buffers.
+pub trait TruncatingRecvMsgVectoredExt: TruncatingRecvMsgVectored {
+    /// Like [`.recv_trunc()`](TruncatingRecvMsgExt::recv_trunc), but receives one message into the
+    /// sequence of buffers given by `bufs`, filling each one to capacity before moving on to the
+    /// next.
+    ///
+    /// See [`TruncatingRecvMsgVectored::poll_recv_trunc_vectored()`] for implementation contract
+    /// notes.
+    fn recv_trunc_vectored<'io, 'bufs, 'slice, 'abuf>(
+        &'io mut self,
+        peek: bool,
+        bufs: &'bufs mut [MsgBuf<'slice>],
+        abuf: Option<&'abuf mut Self::AddrBuf>,
+    ) -> RecvTruncVectored<'io, 'bufs, 'slice, 'abuf, Self>
+    where
+        Self: Unpin,
+    {
+        RecvTruncVectored { recver: self, peek, bufs, abuf }
+    }
+}
+impl<T: TruncatingRecvMsgVectored + ?Sized> TruncatingRecvMsgVectoredExt for T {}
+
 /// Futures for asynchronously receiving from socket-like connections with message boundaries
 /// reliably, without truncation.
 pub trait RecvMsgExt: RecvMsg {
@@ -91,3 +114,45 @@ pub trait RecvMsgExt: RecvMsg {
     }
 }
 impl<T: RecvMsg + ?Sized> RecvMsgExt for T {}
+
+/// Futures for asynchronously receiving from socket-like connections with message boundaries
+/// reliably, without truncation, scattering each message across multiple buffers.
+pub trait RecvMsgVectoredExt: RecvMsgVectored {
+    /// Receives one message into the sequence of buffers given by `bufs`, (re)allocating the last
+    /// buffer if necessary.
+    ///
+    /// See [`sync::RecvMsgVectored::recv_msg_vectored()`](crate::sync::RecvMsgVectored::recv_msg_vectored)
+    /// for details.
+    #[inline]
+    fn recv_msg_vectored<'io, 'bufs, 'slice: 'bufs, 'abuf>(
+        &'io mut self,
+        bufs: &'bufs mut [MsgBuf<'slice>],
+        abuf: Option<&'abuf mut Self::AddrBuf>,
+    ) -> RecvVectored<'io, 'bufs, 'slice, 'abuf, Self>
+    where
+        Self: Unpin,
+    {
+        RecvVectored { recver: self, bufs, abuf }
+    }
+}
+impl<T: RecvMsgVectored + ?Sized> RecvMsgVectoredExt for T {}
+
+/// Futures for asynchronously receiving multiple messages in one call from socket-like connections
+/// with message boundaries reliably, without truncation.
+pub trait RecvMsgBatchExt: RecvMsgBatch {
+    /// Receives one or more messages into the buffers of `batch`, (re)allocating them if necessary.
+    ///
+    /// See [`sync::RecvMsgBatch::recv_msg_batch()`](crate::sync::RecvMsgBatch::recv_msg_batch) for
+    /// details.
+    #[inline]
+    fn recv_msg_batch<'io, 'batch, 'slice: 'batch>(
+        &'io mut self,
+        batch: &'batch mut MsgBatch<'slice, Self::AddrBuf>,
+    ) -> RecvBatch<'io, 'batch, 'slice, Self>
+    where
+        Self: Unpin,
+    {
+        RecvBatch { recver: self, batch }
+    }
+}
+impl<T: RecvMsgBatch + ?Sized> RecvMsgBatchExt for T {}
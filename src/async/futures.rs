@@ -1,5 +1,5 @@
 use super::*;
-use crate::MsgBuf;
+use crate::{MsgBatch, MsgBuf};
 use core::future::Future;
 
 macro_rules! futdoc {
@@ -38,6 +38,24 @@ impl<TRM: TruncatingRecvMsg + Unpin + ?Sized> Future for DiscardMsg<'_, TRM> {
     }
 }
 
+futdoc! { TruncatingRecvMsgVectoredExt::recv_trunc_vectored
+#[derive(Debug)]
+pub struct RecvTruncVectored<'io, 'bufs, 'slice, 'abuf, TRMV: TruncatingRecvMsgVectored + ?Sized> {
+    pub(super) recver: &'io mut TRMV,
+    pub(super) peek: bool,
+    pub(super) bufs: &'bufs mut [MsgBuf<'slice>],
+    pub(super) abuf: Option<&'abuf mut TRMV::AddrBuf>,
+}}
+impl<TRMV: TruncatingRecvMsgVectored + Unpin + ?Sized> Future
+    for RecvTruncVectored<'_, '_, '_, '_, TRMV>
+{
+    type Output = Result<Option<bool>, TRMV::Error>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Self { recver, peek, bufs, abuf } = self.get_mut();
+        Pin::new(&mut **recver).poll_recv_trunc_vectored(cx, *peek, bufs, abuf.as_deref_mut())
+    }
+}
+
 futdoc! { TruncatingRecvMsgWithFullSizeExt::recv_trunc_with_full_size
 #[derive(Debug)]
 pub struct RecvTruncWithFullSize<'io, 'buf, 'slice, 'abuf, TRMWFS: TruncatingRecvMsgWithFullSize + ?Sized> {
@@ -140,3 +158,32 @@ impl<'buf, RM: RecvMsg + Unpin + ?Sized> Future for Recv<'_, 'buf, '_, '_, RM> {
         Pin::new(&mut **recver).poll_recv_msg(cx, buf, abuf.as_deref_mut())
     }
 }
+
+futdoc! { RecvMsgVectoredExt::recv_msg_vectored
+#[derive(Debug)]
+pub struct RecvVectored<'io, 'bufs, 'slice, 'abuf, RMV: RecvMsgVectored + ?Sized> {
+    pub(super) recver: &'io mut RMV,
+    pub(super) bufs: &'bufs mut [MsgBuf<'slice>],
+    pub(super) abuf: Option<&'abuf mut RMV::AddrBuf>
+}}
+impl<RMV: RecvMsgVectored + Unpin + ?Sized> Future for RecvVectored<'_, '_, '_, '_, RMV> {
+    type Output = Result<RecvResult, RMV::Error>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let RecvVectored { recver, bufs, abuf } = self.get_mut();
+        Pin::new(&mut **recver).poll_recv_msg_vectored(cx, bufs, abuf.as_deref_mut())
+    }
+}
+
+futdoc! { RecvMsgBatchExt::recv_msg_batch
+#[derive(Debug)]
+pub struct RecvBatch<'io, 'batch, 'slice, RMB: RecvMsgBatch + ?Sized> {
+    pub(super) recver: &'io mut RMB,
+    pub(super) batch: &'batch mut MsgBatch<'slice, RMB::AddrBuf>,
+}}
+impl<RMB: RecvMsgBatch + Unpin + ?Sized> Future for RecvBatch<'_, '_, '_, RMB> {
+    type Output = Result<usize, RMB::Error>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let RecvBatch { recver, batch } = self.get_mut();
+        Pin::new(&mut **recver).poll_recv_msg_batch(cx, batch)
+    }
+}
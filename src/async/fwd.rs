@@ -81,6 +81,42 @@ impl<T: TruncatingRecvMsgWithFullSize + Unpin + ?Sized> TruncatingRecvMsgWithFul
     }
 }
 
+impl<T: TruncatingRecvMsgVectored + ?Sized, P: DerefMut<Target = T> + Unpin>
+    TruncatingRecvMsgVectored for Pin<P>
+{
+    forward_trait_methods! {
+        pin_fn poll_recv_trunc_vectored(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            peek: bool,
+            bufs: &mut [MsgBuf<'_>],
+            abuf: Option<&mut Self::AddrBuf>,
+        ) -> Poll<Result<Option<bool>, Self::Error>>;
+    }
+}
+impl<T: TruncatingRecvMsgVectored + Unpin + ?Sized> TruncatingRecvMsgVectored for &mut T {
+    forward_trait_methods! {
+        deref_fn poll_recv_trunc_vectored(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            peek: bool,
+            bufs: &mut [MsgBuf<'_>],
+            abuf: Option<&mut Self::AddrBuf>,
+        ) -> Poll<Result<Option<bool>, Self::Error>>;
+    }
+}
+impl<T: TruncatingRecvMsgVectored + Unpin + ?Sized> TruncatingRecvMsgVectored for Box<T> {
+    forward_trait_methods! {
+        deref_fn poll_recv_trunc_vectored(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            peek: bool,
+            bufs: &mut [MsgBuf<'_>],
+            abuf: Option<&mut Self::AddrBuf>,
+        ) -> Poll<Result<Option<bool>, Self::Error>>;
+    }
+}
+
 impl<T: RecvMsg + ?Sized, P: DerefMut<Target = T> + Unpin> RecvMsg for Pin<P> {
     type Error = T::Error;
     type AddrBuf = T::AddrBuf;
@@ -117,3 +153,62 @@ impl<T: RecvMsg + Unpin + ?Sized> RecvMsg for Box<T> {
         ) -> Poll<Result<RecvResult, Self::Error>>;
     }
 }
+
+impl<T: RecvMsgVectored + ?Sized, P: DerefMut<Target = T> + Unpin> RecvMsgVectored for Pin<P> {
+    forward_trait_methods! {
+        pin_fn poll_recv_msg_vectored(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            bufs: &mut [MsgBuf<'_>],
+            abuf: Option<&mut Self::AddrBuf>,
+        ) -> Poll<Result<RecvResult, Self::Error>>;
+    }
+}
+impl<T: RecvMsgVectored + Unpin + ?Sized> RecvMsgVectored for &mut T {
+    forward_trait_methods! {
+        deref_fn poll_recv_msg_vectored(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            bufs: &mut [MsgBuf<'_>],
+            abuf: Option<&mut Self::AddrBuf>,
+        ) -> Poll<Result<RecvResult, Self::Error>>;
+    }
+}
+impl<T: RecvMsgVectored + Unpin + ?Sized> RecvMsgVectored for Box<T> {
+    forward_trait_methods! {
+        deref_fn poll_recv_msg_vectored(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            bufs: &mut [MsgBuf<'_>],
+            abuf: Option<&mut Self::AddrBuf>,
+        ) -> Poll<Result<RecvResult, Self::Error>>;
+    }
+}
+
+impl<T: RecvMsgBatch + ?Sized, P: DerefMut<Target = T> + Unpin> RecvMsgBatch for Pin<P> {
+    forward_trait_methods! {
+        pin_fn poll_recv_msg_batch(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            batch: &mut MsgBatch<'_, Self::AddrBuf>,
+        ) -> Poll<Result<usize, Self::Error>>;
+    }
+}
+impl<T: RecvMsgBatch + Unpin + ?Sized> RecvMsgBatch for &mut T {
+    forward_trait_methods! {
+        deref_fn poll_recv_msg_batch(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            batch: &mut MsgBatch<'_, Self::AddrBuf>,
+        ) -> Poll<Result<usize, Self::Error>>;
+    }
+}
+impl<T: RecvMsgBatch + Unpin + ?Sized> RecvMsgBatch for Box<T> {
+    forward_trait_methods! {
+        deref_fn poll_recv_msg_batch(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            batch: &mut MsgBatch<'_, Self::AddrBuf>,
+        ) -> Poll<Result<usize, Self::Error>>;
+    }
+}
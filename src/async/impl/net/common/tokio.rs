@@ -127,3 +127,154 @@
         }
     }
 };};}
+
+#[rustfmt::skip] macro_rules! impl_atrmv {
+(for $ty:ty, with $lfn:path) => { const _: () = {
+    use $crate::{r#async::ioloop, AsyncTruncatingRecvMsgVectored, MsgBuf};
+    use ::std::{
+        io,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    impl AsyncTruncatingRecvMsgVectored for &$ty {
+        fn poll_recv_trunc_vectored(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            peek: bool,
+            bufs: &mut [MsgBuf<'_>],
+            mut abuf: Option<&mut Self::AddrBuf>,
+        ) -> Poll<io::Result<Option<bool>>> {
+            ioloop(
+                self.get_mut(),
+                cx,
+                |slf: &mut Self| $lfn(slf, peek, bufs, abuf.as_deref_mut()),
+                |slf: &mut Self, cx| slf.poll_recv_ready(cx),
+            )
+        }
+    }
+    impl AsyncTruncatingRecvMsgVectored for $ty {
+        #[inline]
+        fn poll_recv_trunc_vectored(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            peek: bool,
+            bufs: &mut [MsgBuf<'_>],
+            abuf: Option<&mut Self::AddrBuf>,
+        ) -> Poll<io::Result<Option<bool>>> {
+            Pin::new(&mut &*self).poll_recv_trunc_vectored(cx, peek, bufs, abuf)
+        }
+    }
+};};}
+
+#[rustfmt::skip] macro_rules! impl_armv {
+(for $ty:ty, with $lfn:path) => { const _: () = {
+    use $crate::{r#async::ioloop, AsyncRecvMsgVectored, MsgBuf, RecvResult};
+    use ::std::{
+        io,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    impl AsyncRecvMsgVectored for &$ty {
+        fn poll_recv_msg_vectored(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            bufs: &mut [MsgBuf<'_>],
+            mut abuf: Option<&mut Self::AddrBuf>,
+        ) -> Poll<io::Result<RecvResult>> {
+            ioloop(
+                self.get_mut(),
+                cx,
+                |slf: &mut Self| $lfn(slf, bufs, abuf.as_deref_mut()),
+                |slf: &mut Self, cx| slf.poll_recv_ready(cx),
+            )
+        }
+    }
+    impl AsyncRecvMsgVectored for $ty {
+        #[inline]
+        fn poll_recv_msg_vectored(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            bufs: &mut [MsgBuf<'_>],
+            abuf: Option<&mut Self::AddrBuf>,
+        ) -> Poll<io::Result<RecvResult>> {
+            Pin::new(&mut &*self).poll_recv_msg_vectored(cx, bufs, abuf)
+        }
+    }
+};};}
+
+#[rustfmt::skip] macro_rules! impl_armb {
+(for $ty:ty, with $lfn:path) => { const _: () = {
+    use $crate::{r#async::ioloop, AsyncRecvMsgBatch, MsgBatch};
+    use ::std::{
+        io,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    impl AsyncRecvMsgBatch for &$ty {
+        fn poll_recv_msg_batch(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            batch: &mut MsgBatch<'_, Self::AddrBuf>,
+        ) -> Poll<io::Result<usize>> {
+            ioloop(
+                self.get_mut(),
+                cx,
+                |slf: &mut Self| $lfn(slf, batch),
+                |slf: &mut Self, cx| slf.poll_recv_ready(cx),
+            )
+        }
+    }
+    impl AsyncRecvMsgBatch for $ty {
+        #[inline]
+        fn poll_recv_msg_batch(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            batch: &mut MsgBatch<'_, Self::AddrBuf>,
+        ) -> Poll<io::Result<usize>> {
+            Pin::new(&mut &*self).poll_recv_msg_batch(cx, batch)
+        }
+    }
+};};}
+
+#[cfg(unix)]
+#[rustfmt::skip] macro_rules! impl_armc {
+(for $ty:ty, with $lfn:path) => { const _: () = {
+    use $crate::{os::unix::{AsyncRecvMsgWithCmsg, CmsgBuf}, r#async::ioloop, MsgBuf, RecvResult};
+    use ::std::{
+        io,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    impl AsyncRecvMsgWithCmsg for &$ty {
+        fn poll_recv_msg_with_cmsg(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut MsgBuf<'_>,
+            cbuf: &mut CmsgBuf<'_>,
+            mut abuf: Option<&mut Self::AddrBuf>,
+        ) -> Poll<io::Result<RecvResult>> {
+            ioloop(
+                self.get_mut(),
+                cx,
+                |slf: &mut Self| $lfn(slf, buf, cbuf, abuf.as_deref_mut()),
+                |slf: &mut Self, cx| slf.poll_recv_ready(cx),
+            )
+        }
+    }
+    impl AsyncRecvMsgWithCmsg for $ty {
+        #[inline]
+        fn poll_recv_msg_with_cmsg(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut MsgBuf<'_>,
+            cbuf: &mut CmsgBuf<'_>,
+            abuf: Option<&mut Self::AddrBuf>,
+        ) -> Poll<io::Result<RecvResult>> {
+            Pin::new(&mut &*self).poll_recv_msg_with_cmsg(cx, buf, cbuf, abuf)
+        }
+    }
+};};}
@@ -62,3 +62,269 @@ the memory allocator decides to be smarter than usual and give us a huge buffer
     assert_eq!(abuf1.port(), p2);
     assert_eq!(abuf2.port(), p1);
 }
+
+#[cfg(unix)]
+#[tokio::test]
+async fn fds() {
+    use crate::{
+        os::unix::{AsyncRecvMsgWithCmsgExt, CmsgBuf},
+        sync::r#impl::net::tests::{check_passed_fd, send_with_fds},
+    };
+    use std::os::unix::io::AsFd;
+    use tokio::net::UnixDatagram;
+
+    let (s1, mut s2) = UnixDatagram::pair().expect("socket pair creation failed");
+    let (p1, _p2) = UnixDatagram::pair().expect("socket pair creation failed");
+    send_with_fds(s1.as_fd(), b"an fd", &[p1.as_fd()]);
+
+    let mut buf = MsgBuf::from(Vec::with_capacity(16));
+    let mut cbuf = CmsgBuf::default();
+    let mut fds = Vec::new();
+    let rslt =
+        s2.recv_msg_with_fds(&mut buf, &mut cbuf, &mut fds, None).await.expect("receive failed");
+    // Only the control message buffer had to grow
+    assert!(matches!(rslt, RecvResult::Fit));
+    assert_eq!(buf.filled_part(), b"an fd");
+    assert_eq!(fds.len(), 1);
+    check_passed_fd(fds[0].as_fd(), p1.as_fd());
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[tokio::test]
+async fn credentials() {
+    use crate::os::unix::{set_pass_credentials, AsyncRecvMsgWithCredentialsExt};
+    use tokio::net::UnixDatagram;
+
+    let (s1, mut s2) = UnixDatagram::pair().expect("socket pair creation failed");
+    set_pass_credentials(&s2, true).expect("SO_PASSCRED setup failed");
+    s1.send(b"who am I").await.expect("send failed");
+
+    let mut buf = MsgBuf::from(Vec::with_capacity(16));
+    let mut creds = None;
+    let rslt =
+        s2.recv_msg_with_credentials(&mut buf, &mut creds, None).await.expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Fit));
+    assert_eq!(buf.filled_part(), b"who am I");
+    let creds = creds.expect("no credentials received");
+    assert_eq!(creds.pid as u32, std::process::id());
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[tokio::test]
+async fn timestamp() {
+    use crate::os::unix::{set_timestamp_ns, AsyncRecvMsgWithTimestampExt};
+    use std::time::SystemTime;
+
+    let addr = Ipv4Addr::LOCALHOST;
+    let (s1, mut s2) =
+        try_join!(UdpSocket::bind((addr, 0)), UdpSocket::bind((addr, 0))).expect("bind failed");
+    s1.connect(s2.local_addr().expect("address query failed")).await.expect("connect failed");
+    set_timestamp_ns(&s2, true).expect("SO_TIMESTAMPNS setup failed");
+
+    let before = SystemTime::now();
+    s1.send(b"tick").await.expect("send failed");
+
+    let mut buf = MsgBuf::from(Vec::with_capacity(16));
+    let mut ts = None;
+    s2.recv_msg_with_timestamp(&mut buf, &mut ts, None).await.expect("receive failed");
+    assert_eq!(buf.filled_part(), b"tick");
+    assert!(SystemTime::from(ts.expect("no timestamp received")) >= before);
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[tokio::test]
+async fn packet_info() {
+    use crate::os::unix::{set_recv_packet_info, AsyncRecvMsgWithPacketInfoExt};
+
+    let addr = Ipv4Addr::LOCALHOST;
+    let (s1, mut s2) =
+        try_join!(UdpSocket::bind((addr, 0)), UdpSocket::bind((addr, 0))).expect("bind failed");
+    s1.connect(s2.local_addr().expect("address query failed")).await.expect("connect failed");
+    set_recv_packet_info(&s2, true).expect("packet info setup failed");
+    s1.send(b"where am I").await.expect("send failed");
+
+    let mut buf = MsgBuf::from(Vec::with_capacity(16));
+    let mut pi = None;
+    s2.recv_msg_with_packet_info(&mut buf, &mut pi, None).await.expect("receive failed");
+    assert_eq!(buf.filled_part(), b"where am I");
+    assert_eq!(pi.expect("no packet info received").dst, IpAddr::from(addr));
+}
+
+#[tokio::test]
+async fn vectored() {
+    use crate::AsyncRecvMsgVectoredExt;
+
+    #[repr(C, align(8))]
+    struct Header([MaybeUninit<u8>; 8]);
+
+    let addr = Ipv4Addr::LOCALHOST;
+    let (s1, mut s2) =
+        try_join!(UdpSocket::bind((addr, 0)), UdpSocket::bind((addr, 0))).expect("bind failed");
+    s1.connect(s2.local_addr().expect("address query failed")).await.expect("connect failed");
+
+    let msg = b"HEADER!!and a payload that doesn't fit into the small buffer";
+    s1.send(msg).await.expect("send failed");
+
+    let mut hdr = Header([MaybeUninit::new(0); 8]);
+    let mut bufa = [MaybeUninit::new(0); 6];
+    let mut bufs = [MsgBuf::from(hdr.0.as_mut()), MsgBuf::from(bufa.as_mut())];
+    let rslt = s2.recv_msg_vectored(&mut bufs, None).await.expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Spilled));
+    assert_eq!(bufs[0].capacity(), 8);
+    assert_eq!(bufs[0].filled_part(), &msg[..8]);
+    assert_eq!(bufs[1].filled_part(), &msg[8..]);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn batch() {
+    use crate::{AsyncRecvMsgBatchExt, MsgBatch};
+
+    let addr = Ipv4Addr::LOCALHOST;
+    let (s1, mut s2) =
+        try_join!(UdpSocket::bind((addr, 0)), UdpSocket::bind((addr, 0))).expect("bind failed");
+    s1.connect(s2.local_addr().expect("address query failed")).await.expect("connect failed");
+    let p1 = s1.local_addr().expect("address query failed").port();
+    let msgs: [&[u8]; 3] = [b"first", b"second, which is longer than the first", b"third"];
+
+    let mut batch = MsgBatch::with_addrs(
+        vec![
+            MsgBuf::from(Vec::with_capacity(64)),
+            MsgBuf::from(Vec::with_capacity(64)),
+            MsgBuf::from(Vec::with_capacity(64)),
+            MsgBuf::from(Vec::with_capacity(64)),
+        ],
+        vec![SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0).into(); 4],
+    );
+    for msg in msgs {
+        s1.send(msg).await.expect("send failed");
+    }
+    let n = s2.recv_msg_batch(&mut batch).await.expect("batch receive failed");
+    assert_eq!(n, 3);
+    assert!(batch.results().iter().all(|r| matches!(r, RecvResult::Fit)));
+    assert!(batch.msgs().eq(msgs.iter().copied()));
+    assert!(batch.addrs[..n].iter().all(|a| a.port() == p1));
+
+    // Waits for the first message of the next batch.
+    let (n, _) = try_join!(s2.recv_msg_batch(&mut batch), s1.send(msgs[2]))
+        .expect("second batch receive failed");
+    assert_eq!(n, 1);
+    assert!(batch.msgs().eq([msgs[2]]));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn seqpacket() {
+    use crate::os::unix::seqpacket::tokio::{UnixSeqpacket, UnixSeqpacketListener};
+
+    let path = std::env::temp_dir().join(format!("recvmsg-aseqpacket-{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let listener = UnixSeqpacketListener::bind(&path).expect("bind failed");
+    let (client, accepted) = tokio::join!(UnixSeqpacket::connect(&path), listener.accept());
+    let (client, (mut server, _)) =
+        (client.expect("connect failed"), accepted.expect("accept failed"));
+    let _ = std::fs::remove_file(&path);
+
+    let mut buf = MsgBuf::from(Vec::with_capacity(4));
+    let (rslt, sent) = tokio::join!(server.recv_msg(&mut buf, None), async {
+        let msg = b"sent after the receiver has started waiting";
+        client.send(msg).await.map(|_| msg)
+    });
+    let msg = sent.expect("send failed");
+    assert!(matches!(rslt.expect("receive failed"), RecvResult::Spilled));
+    assert_eq!(buf.filled_part(), msg);
+
+    drop(client);
+    let rslt = server.recv_msg(&mut buf, None).await.expect("receive failed");
+    assert!(matches!(rslt, RecvResult::EndOfStream));
+}
+
+#[tokio::test]
+async fn oversize() {
+    use crate::OversizePolicy;
+
+    let addr = Ipv4Addr::LOCALHOST;
+    let (s1, mut s2) =
+        try_join!(UdpSocket::bind((addr, 0)), UdpSocket::bind((addr, 0))).expect("bind failed");
+    s1.connect(s2.local_addr().expect("address query failed")).await.expect("connect failed");
+    let msg = b"This message does not fit into sixteen bytes";
+    let next = b"This one does";
+    let mut buf = MsgBuf::from(Vec::with_capacity(16));
+    buf.quota = Some(16);
+
+    buf.oversize = OversizePolicy::Discard;
+    s1.send(msg).await.expect("send failed");
+    s1.send(next).await.expect("send failed");
+    let rslt = s2.recv_msg(&mut buf, None).await.expect("receive failed");
+    assert!(matches!(rslt, RecvResult::QuotaExceeded(..)));
+    let rslt = s2.recv_msg(&mut buf, None).await.expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Fit));
+    assert_eq!(buf.filled_part(), next);
+
+    buf.oversize = OversizePolicy::Truncate;
+    s1.send(msg).await.expect("send failed");
+    let rslt = s2.recv_msg(&mut buf, None).await.expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Truncated { .. }));
+    assert_eq!(buf.filled_part(), &msg[..16]);
+}
+
+#[tokio::test]
+async fn alloc_failed() {
+    use crate::AsyncRecvMsgVectoredExt;
+
+    let addr = Ipv4Addr::LOCALHOST;
+    let (s1, mut s2) =
+        try_join!(UdpSocket::bind((addr, 0)), UdpSocket::bind((addr, 0))).expect("bind failed");
+    s1.connect(s2.local_addr().expect("address query failed")).await.expect("connect failed");
+    let msg = b"Stays in the queue until there's memory for it";
+    s1.send(msg).await.expect("send failed");
+
+    // A buffer that is not allowed to allocate cannot receive anything.
+    let mut buf = MsgBuf::from(());
+    let rslt = s2.recv_msg(&mut buf, None).await.expect("first receive failed");
+    assert!(matches!(rslt, RecvResult::AllocFailed(..)));
+    assert!(!buf.has_msg);
+
+    let mut bufs = [MsgBuf::from(Vec::with_capacity(4)), MsgBuf::from(())];
+    let rslt = s2.recv_msg_vectored(&mut bufs, None).await.expect("second receive failed");
+    assert!(matches!(rslt, RecvResult::AllocFailed(..)));
+    assert!(bufs.iter().all(|b| !b.has_msg));
+
+    let mut buf = MsgBuf::from(Vec::new());
+    let rslt = s2.recv_msg(&mut buf, None).await.expect("third receive failed");
+    assert!(matches!(rslt, RecvResult::Spilled));
+    assert_eq!(buf.filled_part(), msg);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn cmsg_alloc_failed() {
+    use crate::{
+        os::unix::{AsyncRecvMsgWithCmsgExt, CmsgBuf},
+        sync::r#impl::net::tests::{check_passed_fd, send_with_fds},
+    };
+    use std::os::unix::io::AsFd;
+    use tokio::net::UnixDatagram;
+
+    let (s1, mut s2) = UnixDatagram::pair().expect("socket pair creation failed");
+    let (p1, _p2) = UnixDatagram::pair().expect("socket pair creation failed");
+    send_with_fds(s1.as_fd(), b"an fd", &[p1.as_fd()]);
+
+    let mut buf = MsgBuf::from(Vec::with_capacity(16));
+    let mut cbuf = CmsgBuf::new(MsgBuf::from(()));
+    let rslt =
+        s2.recv_msg_with_cmsg(&mut buf, &mut cbuf, None).await.expect("first receive failed");
+    assert!(matches!(rslt, RecvResult::AllocFailed(..)));
+    assert!(!buf.has_msg);
+
+    let mut cbuf = CmsgBuf::default();
+    let mut fds = Vec::new();
+    let rslt = s2
+        .recv_msg_with_fds(&mut buf, &mut cbuf, &mut fds, None)
+        .await
+        .expect("second receive failed");
+    assert!(matches!(rslt, RecvResult::Fit));
+    assert_eq!(buf.filled_part(), b"an fd");
+    assert_eq!(fds.len(), 1);
+    check_passed_fd(fds[0].as_fd(), p1.as_fd());
+}
@@ -1,4 +1,8 @@
-use crate::{sync::r#impl::net::unix::wrap as syncimpl, MsgBuf, RecvResult};
+use crate::{
+    os::unix::{seqpacket::tokio::UnixSeqpacket, CmsgBuf},
+    sync::r#impl::net::unix::{batch, wrap as syncimpl},
+    MsgBatch, MsgBuf, RecvResult,
+};
 use std::{
     io,
     net::SocketAddr as InetAddr,
@@ -15,7 +19,7 @@ fn recv_trunc_ip(
     syncimpl::recv_trunc_ip(slf.as_fd(), peek, buf, abuf)
 }
 fn recv_trunc_unix(
-    slf: &mut &UnixDatagram,
+    slf: &mut &impl AsFd,
     peek: bool,
     buf: &mut MsgBuf<'_>,
     abuf: Option<&mut UnixAddr>,
@@ -30,12 +34,70 @@ fn recv_msg_ip(
     syncimpl::recv_msg_ip(slf.as_fd(), buf, abuf)
 }
 fn recv_msg_unix(
-    slf: &UnixDatagram,
+    slf: &mut &impl AsFd,
     buf: &mut MsgBuf<'_>,
     abuf: Option<&mut UnixAddr>,
 ) -> io::Result<RecvResult> {
     syncimpl::recv_msg_unix(slf.as_fd(), buf, abuf)
 }
+fn recv_trunc_vectored_ip(
+    slf: &mut &UdpSocket,
+    peek: bool,
+    bufs: &mut [MsgBuf<'_>],
+    abuf: Option<&mut InetAddr>,
+) -> io::Result<Option<bool>> {
+    syncimpl::recv_trunc_vectored_ip(slf.as_fd(), peek, bufs, abuf)
+}
+fn recv_trunc_vectored_unix(
+    slf: &mut &impl AsFd,
+    peek: bool,
+    bufs: &mut [MsgBuf<'_>],
+    abuf: Option<&mut UnixAddr>,
+) -> io::Result<Option<bool>> {
+    syncimpl::recv_trunc_vectored_unix(slf.as_fd(), peek, bufs, abuf)
+}
+fn recv_msg_vectored_ip(
+    slf: &mut &UdpSocket,
+    bufs: &mut [MsgBuf<'_>],
+    abuf: Option<&mut InetAddr>,
+) -> io::Result<RecvResult> {
+    syncimpl::recv_msg_vectored_ip(slf.as_fd(), bufs, abuf)
+}
+fn recv_msg_vectored_unix(
+    slf: &mut &impl AsFd,
+    bufs: &mut [MsgBuf<'_>],
+    abuf: Option<&mut UnixAddr>,
+) -> io::Result<RecvResult> {
+    syncimpl::recv_msg_vectored_unix(slf.as_fd(), bufs, abuf)
+}
+fn recv_msg_batch_ip(
+    slf: &mut &UdpSocket,
+    batch: &mut MsgBatch<'_, InetAddr>,
+) -> io::Result<usize> {
+    batch::recv_msg_batch(slf.as_fd(), batch, syncimpl::extract_ip_address)
+}
+fn recv_msg_batch_unix(
+    slf: &mut &UnixDatagram,
+    batch: &mut MsgBatch<'_, UnixAddr>,
+) -> io::Result<usize> {
+    batch::recv_msg_batch(slf.as_fd(), batch, syncimpl::extract_unix_address)
+}
+fn recv_msg_with_cmsg_ip(
+    slf: &mut &UdpSocket,
+    buf: &mut MsgBuf<'_>,
+    cbuf: &mut CmsgBuf<'_>,
+    abuf: Option<&mut InetAddr>,
+) -> io::Result<RecvResult> {
+    syncimpl::recv_msg_with_cmsg_ip(slf.as_fd(), buf, cbuf, abuf)
+}
+fn recv_msg_with_cmsg_unix(
+    slf: &mut &impl AsFd,
+    buf: &mut MsgBuf<'_>,
+    cbuf: &mut CmsgBuf<'_>,
+    abuf: Option<&mut UnixAddr>,
+) -> io::Result<RecvResult> {
+    syncimpl::recv_msg_with_cmsg_unix(slf.as_fd(), buf, cbuf, abuf)
+}
 #[cfg(any(target_os = "linux", target_os = "android"))]
 fn recv_trunc_with_full_size_ip(
     slf: &mut &UdpSocket,
@@ -47,7 +109,7 @@ fn recv_trunc_with_full_size_ip(
 }
 #[cfg(any(target_os = "linux", target_os = "android"))]
 fn recv_trunc_with_full_size_unix(
-    slf: &mut &UnixDatagram,
+    slf: &mut &impl AsFd,
     peek: bool,
     buf: &mut MsgBuf<'_>,
     abuf: Option<&mut UnixAddr>,
@@ -57,11 +119,30 @@ fn recv_trunc_with_full_size_unix(
 
 impl_atrm!(for net::UdpSocket, with recv_trunc_ip, sa InetAddr);
 impl_atrm!(for net::UnixDatagram, with recv_trunc_unix, sa UnixAddr);
+impl_atrm!(for UnixSeqpacket, with recv_trunc_unix, sa UnixAddr);
 
 impl_arm!(for net::UdpSocket, with recv_msg_ip, sa InetAddr);
 impl_arm!(for net::UnixDatagram, with recv_msg_unix, sa UnixAddr);
+impl_arm!(for UnixSeqpacket, with recv_msg_unix, sa UnixAddr);
+
+impl_atrmv!(for net::UdpSocket, with recv_trunc_vectored_ip);
+impl_atrmv!(for net::UnixDatagram, with recv_trunc_vectored_unix);
+impl_atrmv!(for UnixSeqpacket, with recv_trunc_vectored_unix);
+
+impl_armv!(for net::UdpSocket, with recv_msg_vectored_ip);
+impl_armv!(for net::UnixDatagram, with recv_msg_vectored_unix);
+impl_armv!(for UnixSeqpacket, with recv_msg_vectored_unix);
+
+impl_armb!(for net::UdpSocket, with recv_msg_batch_ip);
+impl_armb!(for net::UnixDatagram, with recv_msg_batch_unix);
+
+impl_armc!(for net::UdpSocket, with recv_msg_with_cmsg_ip);
+impl_armc!(for net::UnixDatagram, with recv_msg_with_cmsg_unix);
+impl_armc!(for UnixSeqpacket, with recv_msg_with_cmsg_unix);
 
 #[cfg(any(target_os = "linux", target_os = "android"))]
 impl_atrmwfs!(for net::UdpSocket, with recv_trunc_with_full_size_ip);
 #[cfg(any(target_os = "linux", target_os = "android"))]
 impl_atrmwfs!(for net::UnixDatagram, with recv_trunc_with_full_size_unix);
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl_atrmwfs!(for UnixSeqpacket, with recv_trunc_with_full_size_unix);
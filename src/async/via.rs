@@ -1,5 +1,5 @@
 use super::*;
-use crate::panic_try_recv_retcon;
+use crate::{sync::clear_all, GrowError, OversizePolicy, QuotaExceeded};
 use core::future::Future;
 
 #[cfg_attr(debug_assertions, track_caller)]
@@ -68,8 +68,12 @@ pub fn poll_recv_via_poll_recv_trunc<TRM: TruncatingRecvMsg + ?Sized>(
             break;
         } else {
             buf.set_fill(0);
-            if let Err(qe) = buf.clear_and_grow() {
-                return Poll::Ready(Ok(RecvResult::QuotaExceeded(qe)));
+            match buf.try_clear_and_grow() {
+                Ok(()) => {}
+                Err(GrowError::QuotaExceeded(qe)) => {
+                    return poll_recv_oversize(slf, cx, buf, abuf, qe);
+                }
+                Err(e) => return Poll::Ready(Ok(e.into())),
             }
         }
     }
@@ -78,31 +82,249 @@ pub fn poll_recv_via_poll_recv_trunc<TRM: TruncatingRecvMsg + ?Sized>(
         Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
         Poll::Pending => panic!(".poll_discard_msg() returned Poll::Pending after successful peek"),
     }
-    Ok(if fit_first { RecvResult::Fit } else { RecvResult::Spilled }).into()
+    Ok(buf.after_recv(if fit_first { RecvResult::Fit } else { RecvResult::Spilled })).into()
+}
+
+/// Implements [`RecvMsgVectored::poll_recv_msg_vectored()`] via
+/// [`TruncatingRecvMsgVectored::poll_recv_trunc_vectored()`], growing the last buffer as necessary.
+///
+/// # Panics
+/// If `bufs` is empty.
+pub fn poll_recv_vectored_via_poll_recv_trunc_vectored<TRMV: TruncatingRecvMsgVectored + ?Sized>(
+    mut slf: Pin<&mut TRMV>,
+    cx: &mut Context<'_>,
+    bufs: &mut [MsgBuf<'_>],
+    mut abuf: Option<&mut TRMV::AddrBuf>,
+) -> Poll<Result<RecvResult, TRMV::Error>> {
+    assert!(!bufs.is_empty(), "no buffers to receive into");
+    let mut first = true;
+    loop {
+        let rr = match slf.as_mut().poll_recv_trunc_vectored(cx, true, bufs, abuf.as_deref_mut()) {
+            Poll::Ready(r) => r,
+            Poll::Pending => {
+                if !first {
+                    #[rustfmt::skip] dbgtrp("\
+.poll_recv_trunc_vectored() returned Poll::Pending after having returned Poll::Ready with \
+peek = true");
+                }
+                return Poll::Pending;
+            }
+        };
+        let fit = match rr {
+            Ok(Some(fit)) => fit,
+            Ok(None) => return Ok(RecvResult::EndOfStream).into(),
+            Err(e) => {
+                clear_all(bufs);
+                return Err(e).into();
+            }
+        };
+        if fit {
+            break;
+        }
+        first = false;
+        clear_all(bufs);
+        match bufs.last_mut().unwrap().try_clear_and_grow() {
+            Ok(()) => {}
+            Err(GrowError::QuotaExceeded(qe)) => {
+                return poll_recv_vectored_oversize(slf, cx, bufs, abuf, qe);
+            }
+            Err(e) => return Poll::Ready(Ok(e.into())),
+        }
+    }
+    match slf.poll_discard_msg(cx) {
+        Poll::Ready(Ok(())) => {}
+        Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+        Poll::Pending => panic!(".poll_discard_msg() returned Poll::Pending after successful peek"),
+    }
+    let rslt = if first { RecvResult::Fit } else { RecvResult::Spilled };
+    Ok(bufs.last_mut().unwrap().after_recv(rslt)).into()
+}
+
+/// Like [`poll_recv_via_poll_recv_trunc()`], but skips peeking if the capacity of `buf` is at least
+/// `max_msg_size`, receiving the message directly instead.
+///
+/// See [`sync::recv_via_recv_trunc_with_max_size()`](crate::sync::recv_via_recv_trunc_with_max_size)
+/// for details, including on how messages that get truncated nonetheless are reported.
+pub fn poll_recv_via_poll_recv_trunc_with_max_size<TRM: TruncatingRecvMsg + ?Sized>(
+    slf: Pin<&mut TRM>,
+    cx: &mut Context<'_>,
+    buf: &mut MsgBuf<'_>,
+    abuf: Option<&mut TRM::AddrBuf>,
+    max_msg_size: usize,
+) -> Poll<Result<RecvResult, TRM::Error>> {
+    if buf.capacity() < max_msg_size {
+        return poll_recv_via_poll_recv_trunc(slf, cx, buf, abuf);
+    }
+    match ready!(slf.poll_recv_trunc(cx, false, buf, abuf)) {
+        Ok(Some(true)) => Ok(buf.after_recv(RecvResult::Fit)),
+        Ok(Some(false)) => Ok(buf.after_recv(RecvResult::Truncated { full_len: None })),
+        Ok(None) => Ok(RecvResult::EndOfStream),
+        Err(e) => {
+            buf.set_fill(0);
+            buf.has_msg = false;
+            Err(e)
+        }
+    }
+    .into()
 }
 
 /// Implements [`RecvMsg::poll_recv_msg()`] via
 /// [`TruncatingRecvMsgWithFullSizeExt::try_recv_msg()`].
+///
+/// See [`sync::recv_via_try_recv()`](crate::sync::recv_via_try_recv) for the receive strategy,
+/// which takes two receive calls for messages that fit and can end up receiving a different
+/// message than the one it peeked if the socket is shared, and
+/// [`poll_recv_via_poll_try_recv_with_max_size()`] for the single-call alternative.
 pub fn poll_recv_via_poll_try_recv<TRMWFS: TruncatingRecvMsgWithFullSize + ?Sized>(
     mut slf: Pin<&mut TRMWFS>,
     cx: &mut Context<'_>,
     buf: &mut MsgBuf<'_>,
     mut abuf: Option<&mut TRMWFS::AddrBuf>,
 ) -> Poll<Result<RecvResult, TRMWFS::Error>> {
-    let mut poll_try_recv =
-        |buf: &mut MsgBuf<'_>| Pin::new(&mut slf.try_recv_msg(buf, abuf.as_deref_mut())).poll(cx);
-    let ok = match ready!(poll_try_recv(buf)?) {
+    let ok = match ready!(Pin::new(&mut slf.try_recv_msg(buf, abuf.as_deref_mut())).poll(cx)?) {
         TryRecvResult::Spilled(sz) => {
-            if let Err(qe) = buf.clear_and_grow_to(sz) {
-                return Ok(RecvResult::QuotaExceeded(qe)).into();
+            match buf.try_clear_and_grow_to(sz) {
+                Ok(()) => {}
+                Err(GrowError::QuotaExceeded(qe)) => {
+                    return poll_try_recv_oversize(slf, cx, buf, abuf, qe);
+                }
+                Err(e) => return Ok(e.into()).into(),
             }
-            match ready!(poll_try_recv(buf)?) {
+            match ready!(slf.poll_recv_trunc_with_full_size(cx, false, buf, abuf)?) {
                 TryRecvResult::Fit => RecvResult::Spilled,
-                TryRecvResult::Spilled(..) => panic_try_recv_retcon(),
+                // Another receiver took the peeked message, and the next one is larger
+                TryRecvResult::Spilled(full_len) => {
+                    RecvResult::Truncated { full_len: Some(full_len) }
+                }
                 TryRecvResult::EndOfStream => return Ok(RecvResult::EndOfStream).into(),
             }
         }
         fit_or_end => fit_or_end.into(),
     };
-    Ok(ok).into()
+    Ok(buf.after_recv(ok)).into()
+}
+
+/// Like [`poll_recv_via_poll_try_recv()`], but receives the message with a single non-peeking
+/// call if the capacity of `buf` is at least `max_msg_size`.
+///
+/// See [`sync::recv_via_try_recv_with_max_size()`](crate::sync::recv_via_try_recv_with_max_size)
+/// for details.
+pub fn poll_recv_via_poll_try_recv_with_max_size<TRMWFS: TruncatingRecvMsgWithFullSize + ?Sized>(
+    slf: Pin<&mut TRMWFS>,
+    cx: &mut Context<'_>,
+    buf: &mut MsgBuf<'_>,
+    abuf: Option<&mut TRMWFS::AddrBuf>,
+    max_msg_size: usize,
+) -> Poll<Result<RecvResult, TRMWFS::Error>> {
+    if buf.capacity() < max_msg_size {
+        return poll_recv_via_poll_try_recv(slf, cx, buf, abuf);
+    }
+    let rslt = match ready!(slf.poll_recv_trunc_with_full_size(cx, false, buf, abuf)) {
+        Ok(TryRecvResult::Fit) => RecvResult::Fit,
+        Ok(TryRecvResult::Spilled(full_len)) => RecvResult::Truncated { full_len: Some(full_len) },
+        Ok(TryRecvResult::EndOfStream) => return Ok(RecvResult::EndOfStream).into(),
+        Err(e) => {
+            buf.set_fill(0);
+            buf.has_msg = false;
+            return Err(e).into();
+        }
+    };
+    Ok(buf.after_recv(rslt)).into()
+}
+
+/// Applies the [oversize policy](OversizePolicy) of `buf` to the message at the front of the
+/// queue, which did not fit into `buf` within its quota.
+///
+/// The async version of `sync::recv_oversize()`.
+fn poll_recv_oversize<TRM: TruncatingRecvMsg + ?Sized>(
+    slf: Pin<&mut TRM>,
+    cx: &mut Context<'_>,
+    buf: &mut MsgBuf<'_>,
+    abuf: Option<&mut TRM::AddrBuf>,
+    qe: QuotaExceeded,
+) -> Poll<Result<RecvResult, TRM::Error>> {
+    buf.set_fill(0);
+    buf.has_msg = false;
+    match buf.oversize {
+        OversizePolicy::LeaveQueued => {}
+        OversizePolicy::Discard => ready!(slf.poll_discard_msg(cx)?),
+        OversizePolicy::Truncate => {
+            if let Err(e) = buf.try_clear_and_grow_to_quota() {
+                return Ok(e.into()).into();
+            }
+            return match ready!(slf.poll_recv_trunc(cx, false, buf, abuf)) {
+                Ok(Some(true)) => Ok(buf.after_recv(RecvResult::Spilled)),
+                Ok(Some(false)) => Ok(buf.after_recv(RecvResult::Truncated { full_len: None })),
+                Ok(None) => Ok(RecvResult::EndOfStream),
+                Err(e) => {
+                    buf.set_fill(0);
+                    buf.has_msg = false;
+                    Err(e)
+                }
+            }
+            .into();
+        }
+    }
+    Ok(RecvResult::QuotaExceeded(qe)).into()
+}
+
+/// Like [`poll_recv_oversize()`], but for vectored reception, in which the last buffer grows.
+fn poll_recv_vectored_oversize<TRMV: TruncatingRecvMsgVectored + ?Sized>(
+    slf: Pin<&mut TRMV>,
+    cx: &mut Context<'_>,
+    bufs: &mut [MsgBuf<'_>],
+    abuf: Option<&mut TRMV::AddrBuf>,
+    qe: QuotaExceeded,
+) -> Poll<Result<RecvResult, TRMV::Error>> {
+    clear_all(bufs);
+    let last = bufs.last_mut().unwrap();
+    match last.oversize {
+        OversizePolicy::LeaveQueued => {}
+        OversizePolicy::Discard => ready!(slf.poll_discard_msg(cx)?),
+        OversizePolicy::Truncate => {
+            if let Err(e) = last.try_clear_and_grow_to_quota() {
+                return Ok(e.into()).into();
+            }
+            return match ready!(slf.poll_recv_trunc_vectored(cx, false, bufs, abuf)) {
+                Ok(Some(fit)) => {
+                    let rslt = if fit {
+                        RecvResult::Spilled
+                    } else {
+                        RecvResult::Truncated { full_len: None }
+                    };
+                    Ok(bufs.last_mut().unwrap().after_recv(rslt))
+                }
+                Ok(None) => Ok(RecvResult::EndOfStream),
+                Err(e) => {
+                    clear_all(bufs);
+                    Err(e)
+                }
+            }
+            .into();
+        }
+    }
+    Ok(RecvResult::QuotaExceeded(qe)).into()
+}
+
+/// Like [`poll_recv_oversize()`], but reports the full size of truncated messages.
+fn poll_try_recv_oversize<TRMWFS: TruncatingRecvMsgWithFullSize + ?Sized>(
+    slf: Pin<&mut TRMWFS>,
+    cx: &mut Context<'_>,
+    buf: &mut MsgBuf<'_>,
+    abuf: Option<&mut TRMWFS::AddrBuf>,
+    qe: QuotaExceeded,
+) -> Poll<Result<RecvResult, TRMWFS::Error>> {
+    if buf.oversize != OversizePolicy::Truncate {
+        return poll_recv_oversize(slf, cx, buf, abuf, qe);
+    }
+    buf.has_msg = false;
+    if let Err(e) = buf.try_clear_and_grow_to_quota() {
+        return Ok(e.into()).into();
+    }
+    let rslt = match ready!(slf.poll_recv_trunc_with_full_size(cx, false, buf, abuf)?) {
+        TryRecvResult::Fit => RecvResult::Spilled,
+        TryRecvResult::Spilled(full_len) => RecvResult::Truncated { full_len: Some(full_len) },
+        TryRecvResult::EndOfStream => RecvResult::EndOfStream,
+    };
+    Ok(buf.after_recv(rslt)).into()
 }
@@ -0,0 +1,88 @@
+use crate::{MsgBuf, NoAddrBuf, RecvResult};
+use alloc::vec::Vec;
+
+/// A set of buffers for receiving multiple messages in one call, along with the results of the
+/// last such reception.
+///
+/// Each message goes into its own [`MsgBuf`], with the `n`-th message of a batch being received
+/// into `bufs[n]`, and its sender address into `addrs[n]` if `addrs` is non-empty.
+///
+/// Where supported, the whole batch is received with a single system call into the buffers as
+/// large as they are at the time, so a message that doesn't fit its buffer is truncated and its
+/// result is [`RecvResult::Truncated`]. The buffer is then grown to fit such a message next time.
+/// Setting [`max_msg_size`](Self::max_msg_size) avoids truncation altogether. Elsewhere, each
+/// message is received on its own, with buffers grown as needed just like with
+/// [`RecvMsg`](crate::RecvMsg).
+#[derive(Debug)]
+pub struct MsgBatch<'slice, AddrBuf = NoAddrBuf> {
+    /// The buffers to receive messages into. The number of buffers is the maximum number of
+    /// messages received in one batch.
+    pub bufs: Vec<MsgBuf<'slice>>,
+    /// The buffers to receive sender addresses into. If empty, addresses are not received.
+    /// Otherwise, must not be shorter than `bufs`.
+    pub addrs: Vec<AddrBuf>,
+    /// The size which no message can exceed, such as 65535 for UDP.
+    ///
+    /// If set, the buffers are grown to this size before receiving, so that no message is
+    /// truncated unless it is larger nonetheless. A buffer that can't be grown this far, along with
+    /// those after it, is left out of the batch. Defaults to `None`, which receives into the
+    /// buffers as they are.
+    pub max_msg_size: Option<usize>,
+    pub(crate) results: Vec<RecvResult>,
+    /// An error that occurred after some messages had already been received, to be returned by the
+    /// next reception.
+    #[cfg(all(feature = "std_net", unix))]
+    pub(crate) deferred_err: Option<std::io::Error>,
+}
+impl<'slice, AddrBuf> MsgBatch<'slice, AddrBuf> {
+    /// Creates a batch with the given message buffers that does not receive sender addresses.
+    #[inline]
+    pub fn new(bufs: Vec<MsgBuf<'slice>>) -> Self {
+        Self {
+            bufs,
+            addrs: Vec::new(),
+            max_msg_size: None,
+            results: Vec::new(),
+            #[cfg(all(feature = "std_net", unix))]
+            deferred_err: None,
+        }
+    }
+    /// Creates a batch with the given message buffers and sender address buffers.
+    ///
+    /// # Panics
+    /// If `addrs` is shorter than `bufs`.
+    #[inline]
+    pub fn with_addrs(bufs: Vec<MsgBuf<'slice>>, addrs: Vec<AddrBuf>) -> Self {
+        assert!(addrs.len() >= bufs.len(), "fewer address buffers than message buffers");
+        Self { addrs, ..Self::new(bufs) }
+    }
+
+    /// Returns the number of results of the last reception, which is the number of messages
+    /// received plus one if the last reception ended on a non-message result (end of stream or
+    /// quota exhaustion).
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+    /// Returns `true` if there are no results.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+    /// Returns the results of the last reception, one for each of the first
+    /// [`.len()`](Self::len) buffers.
+    #[inline]
+    pub fn results(&self) -> &[RecvResult] {
+        &self.results
+    }
+    /// Iterates over the messages received by the last reception.
+    pub fn msgs(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        self.bufs[..self.results.len()].iter().filter_map(MsgBuf::msg)
+    }
+}
+impl<AddrBuf> Default for MsgBatch<'_, AddrBuf> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
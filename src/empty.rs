@@ -1,6 +1,8 @@
 use crate::{
-    AsyncRecvMsg, AsyncTruncatingRecvMsg, AsyncTruncatingRecvMsgWithFullSize, MsgBuf, RecvMsg,
-    RecvResult, TruncatingRecvMsg, TruncatingRecvMsgWithFullSize, TryRecvResult,
+    AsyncRecvMsg, AsyncRecvMsgBatch, AsyncRecvMsgVectored, AsyncTruncatingRecvMsg,
+    AsyncTruncatingRecvMsgVectored, AsyncTruncatingRecvMsgWithFullSize, MsgBatch, MsgBuf, RecvMsg,
+    RecvMsgBatch, RecvMsgVectored, RecvResult, TruncatingRecvMsg, TruncatingRecvMsgVectored,
+    TruncatingRecvMsgWithFullSize, TryRecvResult,
 };
 use core::{
     convert::Infallible,
@@ -51,6 +53,17 @@ impl<AddrBuf> TruncatingRecvMsgWithFullSize for Empty<AddrBuf> {
         Ok(TryRecvResult::EndOfStream)
     }
 }
+impl<AddrBuf> TruncatingRecvMsgVectored for Empty<AddrBuf> {
+    #[inline(always)]
+    fn recv_trunc_vectored(
+        &mut self,
+        _: bool,
+        _: &mut [MsgBuf<'_>],
+        _: Option<&mut AddrBuf>,
+    ) -> Result<Option<bool>, Self::Error> {
+        Ok(None)
+    }
+}
 impl<AddrBuf> RecvMsg for Empty<AddrBuf> {
     type Error = Infallible;
     type AddrBuf = AddrBuf;
@@ -63,6 +76,27 @@ impl<AddrBuf> RecvMsg for Empty<AddrBuf> {
         Ok(RecvResult::EndOfStream)
     }
 }
+impl<AddrBuf> RecvMsgVectored for Empty<AddrBuf> {
+    #[inline(always)]
+    fn recv_msg_vectored(
+        &mut self,
+        _: &mut [MsgBuf<'_>],
+        _: Option<&mut AddrBuf>,
+    ) -> Result<RecvResult, Self::Error> {
+        Ok(RecvResult::EndOfStream)
+    }
+}
+
+impl<AddrBuf> RecvMsgBatch for Empty<AddrBuf> {
+    #[inline(always)]
+    fn recv_msg_batch(&mut self, batch: &mut MsgBatch<'_, AddrBuf>) -> Result<usize, Self::Error> {
+        batch.results.clear();
+        if !batch.bufs.is_empty() {
+            batch.results.push(RecvResult::EndOfStream);
+        }
+        Ok(batch.len())
+    }
+}
 
 impl<AddrBuf> AsyncTruncatingRecvMsg for Empty<AddrBuf> {
     type Error = Infallible;
@@ -90,6 +124,18 @@ impl<AddrBuf> AsyncTruncatingRecvMsgWithFullSize for Empty<AddrBuf> {
         Ok(TryRecvResult::EndOfStream).into()
     }
 }
+impl<AddrBuf> AsyncTruncatingRecvMsgVectored for Empty<AddrBuf> {
+    #[inline(always)]
+    fn poll_recv_trunc_vectored(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        _: bool,
+        _: &mut [MsgBuf<'_>],
+        _: Option<&mut AddrBuf>,
+    ) -> Poll<Result<Option<bool>, Self::Error>> {
+        Ok(None).into()
+    }
+}
 impl<AddrBuf> AsyncRecvMsg for Empty<AddrBuf> {
     type Error = Infallible;
     type AddrBuf = AddrBuf;
@@ -103,3 +149,24 @@ impl<AddrBuf> AsyncRecvMsg for Empty<AddrBuf> {
         Ok(RecvResult::EndOfStream).into()
     }
 }
+impl<AddrBuf> AsyncRecvMsgVectored for Empty<AddrBuf> {
+    #[inline(always)]
+    fn poll_recv_msg_vectored(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        _: &mut [MsgBuf<'_>],
+        _: Option<&mut AddrBuf>,
+    ) -> Poll<Result<RecvResult, Self::Error>> {
+        Ok(RecvResult::EndOfStream).into()
+    }
+}
+impl<AddrBuf> AsyncRecvMsgBatch for Empty<AddrBuf> {
+    #[inline(always)]
+    fn poll_recv_msg_batch(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        batch: &mut MsgBatch<'_, AddrBuf>,
+    ) -> Poll<Result<usize, Self::Error>> {
+        self.recv_msg_batch(batch).into()
+    }
+}
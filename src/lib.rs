@@ -38,16 +38,22 @@
 //! using the appropriate helper function from the corresponding module.
 //!
 //! # Feature flags
-//! - *`std`* – `std::error::Error` on [`QuotaExceeded`]. Precludes `#![no_std]`.
+//! - *`std`* – `std::error::Error` on [`QuotaExceeded`], [`AllocFailed`] and [`GrowError`].
+//!   Precludes `#![no_std]`.
 //! - *`std_net`* – implementations of traits on types from `std::net` and `std::os::unix::net`
 //!   (Unix domain sockets) on Unix.
+//! - *`bytes`* – conversions between `MsgBuf` and the `BytesMut` and `Bytes` types from the
+//!   [`bytes`](https://docs.rs/bytes) crate, a `BufMut` implementation on `MsgBuf`, and
+//!   `BytesMutBuf`, an `OwnedBuf` for receiving into the allocation of a `BytesMut`.
+//! - *`allocator_api2`* – `OwnedBuf` implementation on `Vec<u8, A>` from the
+//!   [`allocator-api2`](https://docs.rs/allocator-api2) crate, for allocating owned buffers with a
+//!   custom allocator. Requires Rust 1.63.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![forbid(unsafe_op_in_unsafe_fn)]
 #![warn(missing_docs, unsafe_code)]
 extern crate alloc;
 
-// TODO vectored
 // TODO async-std
 // TODO from_fns
 
@@ -71,36 +77,67 @@ pub mod os {
     pub mod unix;
 }
 
+mod adaptive;
+mod batch;
 mod empty;
+mod max_msg_size;
 
-pub use {empty::*, msgbuf::QuotaExceeded, prelude::*};
-
-#[track_caller]
-fn panic_try_recv_retcon() -> ! {
-    panic!(
-        "\
-try_recv_msg() returned TryRecvResult::Failed for a buffer of a size that it reported was \
-sufficient"
-    )
-}
+pub use {
+    adaptive::*,
+    batch::*,
+    empty::*,
+    max_msg_size::*,
+    msgbuf::{AllocFailed, GrowError, OversizePolicy, QuotaExceeded},
+    prelude::*,
+};
 
 /// The type of `AddrBuf` associated types on implementations of traits from this crate for types
 /// that do not support receiving the address of the peer together with received messages.
 pub type NoAddrBuf = core::convert::Infallible;
 
 /// Result type for `.recv_msg()` methods.
-#[derive(Copy, Clone, Debug, Default)]
+///
+/// More variants may be added in minor releases.
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
 pub enum RecvResult {
     /// The message stream has ended and no more messages will be received.
-    #[default]
     EndOfStream,
     /// The message successfully fit into the provided buffer and is of the given size.
     Fit,
     /// The message didn't fit into the provided buffer, and has been received into [`MsgBuf`]'s
     /// `owned` field, which has been updated with a new or extended allocation.
     Spilled,
-    /// The buffer size quota was exceeded.
+    /// The buffer size quota was exceeded. Whether the message is still at the front of the queue
+    /// depends on the buffer's [oversize policy](OversizePolicy).
     QuotaExceeded(QuotaExceeded),
+    /// The message would have exceeded the buffer size quota, and has been received truncated to
+    /// the quota as per [`OversizePolicy::Truncate`]. Also returned for a message that turned out
+    /// to be larger than a declared maximum message size, or than its buffer in a
+    /// [`MsgBatch`].
+    Truncated {
+        /// The size of the message before truncation, if known.
+        full_len: Option<usize>,
+    },
+    /// Memory allocation for a larger buffer failed. The message has been left in the queue, so
+    /// that it can be received once memory is available again.
+    AllocFailed(AllocFailed),
+}
+impl Default for RecvResult {
+    /// Returns `EndOfStream`.
+    #[inline]
+    fn default() -> Self {
+        Self::EndOfStream
+    }
+}
+impl From<GrowError> for RecvResult {
+    #[inline]
+    fn from(e: GrowError) -> Self {
+        match e {
+            GrowError::QuotaExceeded(e) => Self::QuotaExceeded(e),
+            GrowError::AllocFailed(e) => Self::AllocFailed(e),
+        }
+    }
 }
 impl From<TryRecvResult> for RecvResult {
     #[inline]
@@ -114,10 +151,9 @@ impl From<TryRecvResult> for RecvResult {
 }
 
 /// Result type for `.try_recv_msg()` and `.recv_trunc_with_full_size()` methods.
-#[derive(Copy, Clone, Debug, Default)]
+#[derive(Copy, Clone, Debug)]
 pub enum TryRecvResult {
     /// The message stream has ended and no more messages will be received.
-    #[default]
     EndOfStream,
     /// The message successfully fit into the provided buffer and is of the given size.
     Fit,
@@ -127,3 +163,10 @@ pub enum TryRecvResult {
     /// - If returned by `.recv_trunc_with_full_size()`, this means that the message was truncated.
     Spilled(usize),
 }
+impl Default for TryRecvResult {
+    /// Returns `EndOfStream`.
+    #[inline]
+    fn default() -> Self {
+        Self::EndOfStream
+    }
+}
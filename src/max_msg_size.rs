@@ -0,0 +1,61 @@
+use crate::{
+    r#async::poll_recv_via_poll_recv_trunc_with_max_size, sync::recv_via_recv_trunc_with_max_size,
+    AsyncRecvMsg, AsyncTruncatingRecvMsg, MsgBuf, RecvMsg, RecvResult, TruncatingRecvMsg,
+};
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Adapter that implements [`RecvMsg`] and [`AsyncRecvMsg`] for a truncating receiver whose
+/// messages are known not to exceed a certain size.
+///
+/// Messages are received without peeking whenever the buffer is at least `max_msg_size` bytes
+/// large, which takes one receive call instead of two and copies the message only once. Smaller
+/// buffers go through the usual peek-and-grow loop. See
+/// [`recv_via_recv_trunc_with_max_size()`](crate::sync::recv_via_recv_trunc_with_max_size).
+///
+/// A message received without peeking that turns out to be larger than `max_msg_size` is returned
+/// truncated, with `RecvResult::Truncated`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct WithMaxMsgSize<T> {
+    /// The underlying truncating receiver.
+    pub inner: T,
+    /// The size which no message received from `inner` can exceed.
+    pub max_msg_size: usize,
+}
+impl<T> WithMaxMsgSize<T> {
+    /// Wraps the given receiver.
+    #[inline]
+    pub fn new(inner: T, max_msg_size: usize) -> Self {
+        Self { inner, max_msg_size }
+    }
+}
+
+impl<T: TruncatingRecvMsg> RecvMsg for WithMaxMsgSize<T> {
+    type Error = T::Error;
+    type AddrBuf = T::AddrBuf;
+    #[inline]
+    fn recv_msg(
+        &mut self,
+        buf: &mut MsgBuf<'_>,
+        abuf: Option<&mut T::AddrBuf>,
+    ) -> Result<RecvResult, Self::Error> {
+        recv_via_recv_trunc_with_max_size(&mut self.inner, buf, abuf, self.max_msg_size)
+    }
+}
+
+impl<T: AsyncTruncatingRecvMsg + Unpin> AsyncRecvMsg for WithMaxMsgSize<T> {
+    type Error = T::Error;
+    type AddrBuf = T::AddrBuf;
+    #[inline]
+    fn poll_recv_msg(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut MsgBuf<'_>,
+        abuf: Option<&mut T::AddrBuf>,
+    ) -> Poll<Result<RecvResult, Self::Error>> {
+        let Self { inner, max_msg_size } = self.get_mut();
+        poll_recv_via_poll_recv_trunc_with_max_size(Pin::new(inner), cx, buf, abuf, *max_msg_size)
+    }
+}
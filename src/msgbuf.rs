@@ -4,22 +4,35 @@
 //! See [`MsgBuf`]'s documentation.
 #![allow(unsafe_code)]
 
-// TODO fallible allocation
-
+mod align;
+mod alloc_err;
+mod buf_pool;
 mod cap;
 mod ctor;
 mod cursors;
+mod decay;
+#[cfg(feature = "bytes")]
+mod impl_bytes;
 mod impl_debug;
+mod inline;
 mod lifetime;
+mod oversize;
 mod owned;
 mod quota_err;
+mod quota_pool;
 mod safe_write;
 mod slicing;
+mod stash;
 mod take;
 #[cfg(test)]
 mod tests;
 
-pub use {owned::*, quota_err::*};
+#[cfg(feature = "bytes")]
+pub use impl_bytes::BytesMutBuf;
+pub use {
+    alloc_err::*, buf_pool::*, decay::DecayPolicy, inline::*, oversize::*, owned::*, quota_err::*,
+    quota_pool::*,
+};
 
 use core::{marker::PhantomData, mem::MaybeUninit, panic::UnwindSafe, ptr::NonNull};
 
@@ -117,6 +130,20 @@ pub struct MsgBuf<'slice> {
     ///
     /// A `Some(0)` quota prevents allocation altogether.
     pub quota: Option<usize>,
+    /// What the reception helpers are to do when a message doesn't fit within the quota.
+    pub oversize: OversizePolicy,
+    /// Whether the reception helpers are to switch back to the borrowed slice the buffer was
+    /// created from after receiving a message small enough to fit there, copying the message over
+    /// and dropping the owned allocation. See
+    /// [`.reset_to_borrowed()`](Self::reset_to_borrowed).
+    pub return_to_borrowed: bool,
+    /// The borrowed slice that was given up in favor of an owned allocation, if any.
+    stash: Option<(NonNull<u8>, usize)>,
+    /// Automatic shrinking after a run of small messages, if any.
+    pub decay: Option<DecayPolicy>,
+    decay_run: decay::DecayRun,
+    /// The shared budget the owned allocation is charged to, if any.
+    pool: Option<QuotaPool>,
 }
 // Who else remembers that this trait is a thing?
 impl UnwindSafe for MsgBuf<'_> {}
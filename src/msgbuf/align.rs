@@ -0,0 +1,53 @@
+use super::{MsgBuf, OwnedBuf, OwnedBufVtable};
+use core::{cmp::min, ptr::NonNull};
+
+fn addr_align(ptr: NonNull<u8>) -> usize {
+    1 << (ptr.as_ptr() as usize).trailing_zeros()
+}
+
+/// Alignment.
+impl MsgBuf<'_> {
+    /// Returns the alignment that the base pointer of the buffer is guaranteed to have, now and
+    /// after any growth.
+    ///
+    /// For owned buffers, this is the [alignment](OwnedBuf::ALIGNMENT) guaranteed by the owned
+    /// buffer type. For borrowed buffers, the alignment of the slice is taken into account as well,
+    /// as is that of the owned buffer type they [spill to](Self::spill_to). Thus, to have a
+    /// borrowed buffer keep a guarantee when it spills, both the slice and the owned buffer type
+    /// have to provide it:
+    /// ```
+    /// # use recvmsg::{msgbuf::AlignedBuf, MsgBuf};
+    /// #[repr(C, align(16))]
+    /// struct Storage([u8; 64]);
+    /// let mut storage = Storage([0; 64]);
+    /// let mut buf = MsgBuf::from(storage.0.as_mut());
+    /// assert_eq!(buf.alignment(), 1); // Would spill to a Vec
+    /// assert!(buf.spill_to::<AlignedBuf<16>>());
+    /// assert_eq!(buf.alignment(), 16);
+    /// buf.grow_to(1024).unwrap();
+    /// assert_eq!(buf.as_ptr() as usize % 16, 0);
+    /// ```
+    pub fn alignment(&self) -> usize {
+        let mut align = self.own_vt.align;
+        if self.borrow.is_some() && self.cap > 0 {
+            align = min(align, addr_align(self.ptr));
+        }
+        if let Some((ptr, _)) = self.stash {
+            align = min(align, addr_align(ptr));
+        }
+        align
+    }
+
+    /// Sets the owned buffer type that the buffer allocates when it has to spill from a borrowed
+    /// slice, which is `Vec<u8>` by default.
+    ///
+    /// Returns `false` and does nothing if the buffer is already owned, since the existing
+    /// allocation is of a different type.
+    pub fn spill_to<Owned: OwnedBuf>(&mut self) -> bool {
+        if self.borrow.is_none() && self.cap > 0 {
+            return false;
+        }
+        self.own_vt = OwnedBufVtable::new::<Owned>();
+        true
+    }
+}
@@ -0,0 +1,52 @@
+use super::QuotaExceeded;
+use core::{
+    fmt::{self, Display, Formatter},
+    num::NonZeroUsize,
+};
+
+/// Error indicating that a buffer's memory allocation failed during an operation that had to
+/// perform one.
+#[derive(Copy, Clone, Debug)]
+pub struct AllocFailed {
+    /// The size which the buffer was to attain.
+    pub attempted_alloc: NonZeroUsize,
+}
+impl Display for AllocFailed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Self { attempted_alloc } = self;
+        write!(f, "failed to reallocate buffer to {attempted_alloc} bytes")
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for AllocFailed {}
+
+/// Error returned by fallible growth operations on [`MsgBuf`](super::MsgBuf).
+#[derive(Copy, Clone, Debug)]
+pub enum GrowError {
+    /// The buffer's memory allocation quota was exceeded.
+    QuotaExceeded(QuotaExceeded),
+    /// The memory allocation failed.
+    AllocFailed(AllocFailed),
+}
+impl From<QuotaExceeded> for GrowError {
+    #[inline]
+    fn from(e: QuotaExceeded) -> Self {
+        Self::QuotaExceeded(e)
+    }
+}
+impl From<AllocFailed> for GrowError {
+    #[inline]
+    fn from(e: AllocFailed) -> Self {
+        Self::AllocFailed(e)
+    }
+}
+impl Display for GrowError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::QuotaExceeded(e) => Display::fmt(e, f),
+            Self::AllocFailed(e) => Display::fmt(e, f),
+        }
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for GrowError {}
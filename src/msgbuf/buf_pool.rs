@@ -0,0 +1,132 @@
+use super::{DynOwnedBuf, MsgBuf, QuotaExceeded};
+use alloc::vec::Vec;
+use core::cmp::max;
+
+/// A pool of owned allocations for reuse by [`MsgBuf`]s.
+///
+/// Buffers are handed out with [`.get()`](Self::get) and taken back either as whole `MsgBuf`s via
+/// [`.recycle()`](Self::recycle) or as owned buffers via [`.put()`](Self::put), the latter being
+/// useful when the allocation has been passed elsewhere with
+/// [`.take_owned()`](MsgBuf::take_owned). The allocations are bucketed by capacity, and the total
+/// capacity retained by the pool is bounded.
+///
+/// The pool has a quota, which is given to every buffer handed out by it. Allocations larger than
+/// the quota are not retained.
+///
+/// There is no internal synchronization – a pool shared by several threads has to be wrapped in a
+/// mutex.
+#[derive(Debug)]
+pub struct MsgBufPool {
+    /// Bucket 𝑖 holds allocations of between 2ⁱ and 2ⁱ⁺¹ bytes.
+    buckets: Vec<Vec<DynOwnedBuf>>,
+    retained: usize,
+    max_retained: usize,
+    quota: Option<usize>,
+}
+impl MsgBufPool {
+    /// Creates an empty pool that retains allocations totalling up to `max_retained` bytes.
+    #[inline]
+    pub fn new(max_retained: usize) -> Self {
+        Self { buckets: Vec::new(), retained: 0, max_retained, quota: None }
+    }
+    /// Creates an empty pool that retains allocations totalling up to `max_retained` bytes and
+    /// hands out buffers with the given quota.
+    #[inline]
+    pub fn with_quota(max_retained: usize, quota: Option<usize>) -> Self {
+        Self { quota, ..Self::new(max_retained) }
+    }
+
+    /// Returns the quota given to the buffers handed out by the pool.
+    #[inline]
+    pub fn quota(&self) -> Option<usize> {
+        self.quota
+    }
+    /// Sets the quota given to the buffers handed out by the pool from now on, dropping the retained
+    /// allocations that exceed it.
+    pub fn set_quota(&mut self, quota: Option<usize>) {
+        self.quota = quota;
+        if let Some(quota) = quota {
+            let mut freed = 0;
+            for bucket in &mut self.buckets {
+                bucket.retain(|owned| {
+                    let cap = owned.as_raw_parts().cap;
+                    let keep = cap <= quota;
+                    if !keep {
+                        freed += cap;
+                    }
+                    keep
+                });
+            }
+            self.retained -= freed;
+        }
+    }
+    /// Returns the total capacity of the allocations retained by the pool.
+    #[inline]
+    pub fn retained(&self) -> usize {
+        self.retained
+    }
+    /// Returns the most the total capacity of the allocations retained by the pool may be.
+    #[inline]
+    pub fn max_retained(&self) -> usize {
+        self.max_retained
+    }
+
+    /// Hands out an owned buffer with at least the given capacity, reusing the smallest suitable
+    /// allocation from the pool if there is one and allocating a new one otherwise.
+    ///
+    /// The quota of the buffer is set to that of the pool, and a [`QuotaExceeded`] error is
+    /// returned if `min_cap` exceeds it, with any allocation taken from the pool put back.
+    pub fn get(&mut self, min_cap: usize) -> Result<MsgBuf<'static>, QuotaExceeded> {
+        let mut buf = self.take(min_cap).map(MsgBuf::from).unwrap_or_default();
+        buf.quota = self.quota;
+        if let Err(e) = buf.grow_to(min_cap) {
+            self.recycle(buf);
+            return Err(e);
+        }
+        Ok(buf)
+    }
+    fn take(&mut self, min_cap: usize) -> Option<DynOwnedBuf> {
+        let first = bucket_of(max(min_cap, 1));
+        for bucket in self.buckets.iter_mut().skip(first) {
+            let best = bucket
+                .iter()
+                .enumerate()
+                .map(|(i, owned)| (i, owned.as_raw_parts().cap))
+                .filter(|&(_, cap)| cap >= min_cap)
+                .min_by_key(|&(_, cap)| cap);
+            if let Some((i, cap)) = best {
+                self.retained -= cap;
+                return Some(bucket.swap_remove(i));
+            }
+        }
+        None
+    }
+
+    /// Returns an owned buffer to the pool, returning `true` if it was retained and `false` if it
+    /// was dropped because it was empty, exceeded the quota or would not fit within the bound on
+    /// retained capacity.
+    pub fn put(&mut self, owned: DynOwnedBuf) -> bool {
+        let cap = owned.as_raw_parts().cap;
+        let fits = self.retained.checked_add(cap).map_or(false, |total| total <= self.max_retained);
+        if cap == 0 || !fits || self.quota.map_or(false, |quota| cap > quota) {
+            return false;
+        }
+        let b = bucket_of(cap);
+        if self.buckets.len() <= b {
+            self.buckets.resize_with(b + 1, Vec::new);
+        }
+        self.buckets[b].push(owned);
+        self.retained += cap;
+        true
+    }
+    /// Returns the owned allocation of the given buffer to the pool, if it has one. See
+    /// [`.put()`](Self::put).
+    #[inline]
+    pub fn recycle(&mut self, mut buf: MsgBuf<'_>) -> bool {
+        buf.take_owned().map_or(false, |owned| self.put(owned))
+    }
+}
+
+fn bucket_of(cap: usize) -> usize {
+    (usize::BITS - 1 - cap.leading_zeros()) as usize
+}
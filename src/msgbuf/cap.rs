@@ -1,4 +1,4 @@
-use super::{super::QuotaExceeded, MsgBuf};
+use super::{super::QuotaExceeded, AllocFailed, DynOwnedBuf, GrowError, MsgBuf};
 use core::{
     cmp::{max, min},
     mem::size_of,
@@ -49,23 +49,153 @@ impl MsgBuf<'_> {
     /// Ensures that the buffer has at least the given capacity, allocating if necessary and
     /// retaining its content up to the fill cursor.
     pub fn grow_to(&mut self, new_cap: usize) -> Result<(), QuotaExceeded> {
+        self.grow_to_with(new_cap, |owned, new_cap| {
+            owned.grow(new_cap); // This performs the safety check
+            Ok(())
+        })
+        .map_err(|e| match e {
+            GrowError::QuotaExceeded(qe) => qe,
+            GrowError::AllocFailed(..) => unreachable!(),
+        })
+    }
+
+    /// Wipes the contents of the buffer and ensures that it has at least the given capacity,
+    /// allocating if necessary.
+    #[inline]
+    pub fn clear_and_grow_to(&mut self, new_cap: usize) -> Result<(), QuotaExceeded> {
+        self.set_fill(0);
+        self.grow_to(new_cap)
+    }
+
+    /// Same as [`.grow()`](Self::grow), but reports memory allocation failure instead of aborting.
+    /// The buffer is left unchanged if an error is returned.
+    #[inline]
+    pub fn try_grow(&mut self) -> Result<(), GrowError> {
+        self.try_grow_to(self.cap + 1)
+    }
+
+    /// Same as [`.clear_and_grow()`](Self::clear_and_grow), but reports memory allocation failure
+    /// instead of aborting.
+    #[inline]
+    pub fn try_clear_and_grow(&mut self) -> Result<(), GrowError> {
+        self.try_clear_and_grow_to(self.cap + 1)
+    }
+
+    /// Same as [`.grow_to()`](Self::grow_to), but reports memory allocation failure instead of
+    /// aborting. The buffer is left unchanged if an error is returned.
+    ///
+    /// Whether allocation failure can actually be detected depends on the owned buffer type – see
+    /// [`OwnedBuf::try_grow()`](super::OwnedBuf::try_grow).
+    pub fn try_grow_to(&mut self, new_cap: usize) -> Result<(), GrowError> {
+        self.grow_to_with(new_cap, DynOwnedBuf::try_grow)
+    }
+
+    /// Same as [`.clear_and_grow_to()`](Self::clear_and_grow_to), but reports memory allocation
+    /// failure instead of aborting.
+    #[inline]
+    pub fn try_clear_and_grow_to(&mut self, new_cap: usize) -> Result<(), GrowError> {
+        self.set_fill(0);
+        self.try_grow_to(new_cap)
+    }
+
+    /// Shrinks the owned allocation down to the given capacity, or to the length of the filled
+    /// part if that is larger, retaining the contents of the latter. Borrowed buffers are left
+    /// alone, as are owned buffers of types that cannot shrink.
+    ///
+    /// Capacity freed this way is refunded to the [pool](super::QuotaPool), if any.
+    pub fn shrink_to(&mut self, min_cap: usize) {
+        let new_cap = max(min_cap, self.fill);
+        if new_cap >= self.cap || self.borrow.is_some() {
+            return;
+        }
+        let (fill, has_msg, old_cap) = (self.fill, self.has_msg, self.cap);
+        let mut owned = match self.take_owned_uncharged() {
+            Some(owned) => owned,
+            None => return,
+        };
+        owned.shrink(new_cap);
+        self.put_owned(owned);
+        if let Some(pool) = &self.pool {
+            pool.refund(old_cap - self.cap);
+        }
+        self.set_fill(fill);
+        self.has_msg = has_msg;
+    }
+    /// Shrinks the owned allocation down to the length of the filled part. See
+    /// [`.shrink_to()`](Self::shrink_to).
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.shrink_to(0)
+    }
+
+    /// Wipes the contents of the buffer and grows it as far as the quota and the
+    /// [pool](super::QuotaPool) allow, or by an unspecified amount if there is neither. The buffer
+    /// keeps its current capacity if the pool has nothing left to give.
+    pub(crate) fn try_clear_and_grow_to_quota(&mut self) -> Result<(), GrowError> {
+        let mut new_cap = self.quota.map_or(self.cap + 1, |quota| max(quota, self.cap));
+        if let Some(pool) = &self.pool {
+            // Borrowed buffers spill into an allocation that is charged for in full
+            let limit = self.owned_cap().saturating_add(pool.available());
+            new_cap = min(new_cap, max(limit, self.cap));
+        }
+        match self.try_clear_and_grow_to(new_cap) {
+            // Only the pool can refuse, having been drained by other buffers in the meantime
+            Err(GrowError::QuotaExceeded(..)) => Ok(()),
+            rslt => rslt,
+        }
+    }
+
+    fn grow_to_with(
+        &mut self,
+        new_cap: usize,
+        grow: impl FnOnce(&mut DynOwnedBuf, usize) -> Result<(), AllocFailed>,
+    ) -> Result<(), GrowError> {
         let old_cap = self.cap;
         let fill = self.fill;
         let new_cap_exact =
             if let (true, Some(new_cap)) = (new_cap > old_cap, NonZeroUsize::new(new_cap)) {
-                self.plan_grow_amortized(new_cap)?
+                let planned = self.plan_grow_amortized(new_cap)?;
+                self.charge_pool(new_cap, planned)?
             } else {
                 return Ok(());
             };
+        let old_owned_cap = self.owned_cap();
+        let (old_init, has_msg) = (self.init, self.has_msg);
         self.init = min(self.init, fill); // Avoids unnecessary copying
         let is_borrowed = self.borrow.is_some();
-        let mut owned = self.take_owned().unwrap_or_default();
+        let own_vt = self.own_vt;
+        // Borrowed buffers spill to an owned buffer of the type set via `.spill_to()`
+        let mut owned = self.take_owned_uncharged().unwrap_or_else(|| DynOwnedBuf::empty(own_vt));
         let borrowed = is_borrowed.then(|| self.take_borrowed()).flatten();
 
-        owned.grow(new_cap_exact); // This performs the safety check
+        if let Err(e) = grow(&mut owned, new_cap_exact) {
+            if let Some(pool) = &self.pool {
+                pool.refund(new_cap_exact - old_owned_cap);
+            }
+            // Put back whichever buffer we had, which failed growth left untouched
+            match borrowed {
+                Some(borrowed) => self.put_slice(borrowed),
+                None => self.put_owned(owned),
+            }
+            unsafe {
+                // SAFETY: it's the same buffer as before
+                self.set_init(old_init);
+            }
+            self.set_fill(fill);
+            self.has_msg = has_msg;
+            return Err(e.into());
+        }
+        if let Some(pool) = &self.pool {
+            // Allocators are allowed to hand out more than was asked for
+            pool.force_charge(owned.as_raw_parts().cap - new_cap_exact);
+        }
         self.put_owned(owned);
         if let Some(borrowed) = borrowed {
             self[..fill].copy_from_slice(&borrowed[..fill]);
+            if own_vt.sensitive {
+                super::wipe(borrowed.as_mut_ptr().cast(), borrowed.len());
+            }
+            self.stash_slice(borrowed);
             unsafe {
                 // SAFETY: it's the filled part of the old buffer
                 self.set_init(fill);
@@ -74,12 +204,4 @@ impl MsgBuf<'_> {
         self.set_fill(fill);
         Ok(())
     }
-
-    /// Wipes the contents of the buffer and ensures that it has at least the given capacity,
-    /// allocating if necessary.
-    #[inline]
-    pub fn clear_and_grow_to(&mut self, new_cap: usize) -> Result<(), QuotaExceeded> {
-        self.set_fill(0);
-        self.grow_to(new_cap)
-    }
 }
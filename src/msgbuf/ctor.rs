@@ -1,5 +1,6 @@
 use super::{
-    owned::OwnedBuf, owned_default, DynOwnedBuf, MsgBuf, MuU8, OwnedBufRawParts, OwnedBufVtable,
+    owned::OwnedBuf, owned_default, DynOwnedBuf, MsgBuf, MuU8, OversizePolicy, OwnedBufRawParts,
+    OwnedBufVtable,
 };
 use alloc::{boxed::Box, vec::Vec};
 use core::{
@@ -20,6 +21,12 @@ impl Default for MsgBuf<'_> {
             fill: 0,
             has_msg: false,
             quota: None,
+            oversize: OversizePolicy::LeaveQueued,
+            return_to_borrowed: false,
+            stash: None,
+            decay: None,
+            decay_run: Default::default(),
+            pool: None,
         }
     }
 }
@@ -58,7 +65,7 @@ impl<'slice> MsgBuf<'slice> {
         self.fill = 0;
     }
     /// Forgets old buffer in place, if there was one, and replaces it with the given `slice`.
-    fn put_slice(&mut self, slice: &'slice mut [MuU8]) {
+    pub(super) fn put_slice(&mut self, slice: &'slice mut [MuU8]) {
         self.ptr = NonNull::new(slice.as_mut_ptr().cast()).unwrap_or(NonNull::dangling());
         self.cap = slice.len();
         self.borrow = Some(PhantomData);
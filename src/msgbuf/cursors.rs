@@ -65,6 +65,9 @@ impl MsgBuf<'_> {
 
     /// Sets the fill cursor of the buffer to the given value.
     ///
+    /// If the owned buffer type is [sensitive](super::OwnedBuf::SENSITIVE), lowering the fill
+    /// cursor overwrites everything in the initialized part past the new value with zeroes.
+    ///
     /// # Panics
     /// If the given length exceeds the initialization cursor.
     #[inline]
@@ -73,8 +76,18 @@ impl MsgBuf<'_> {
             new_len <= self.init,
             "attempt to advance buffer fill cursor past the initialized part"
         );
+        if new_len < self.fill && self.own_vt.sensitive {
+            super::wipe(unsafe { self.ptr.as_ptr().add(new_len) }, self.init - new_len);
+        }
         self.fill = new_len;
     }
+    /// Overwrites the initialized part of the buffer with zeroes and marks it as empty, for use
+    /// with buffers that hold secrets. Works regardless of the owned buffer type.
+    pub fn wipe(&mut self) {
+        super::wipe(self.ptr.as_ptr(), self.init);
+        self.fill = 0;
+        self.has_msg = false;
+    }
 
     /// Does both [`.advance_init_to()`](Self::advance_init_to) and [`.set_fill`](Self::set_fill).
     ///
@@ -0,0 +1,57 @@
+use super::MsgBuf;
+use core::cmp::max;
+
+/// Automatic shrinking of a [`MsgBuf`]'s owned allocation after a run of messages much smaller
+/// than it, so that a single large message does not pin a large allocation forever.
+///
+/// Set via the `decay` field of the buffer, and applied by the reception helpers of this crate
+/// after every received message. Receivers that don't use those helpers can apply it themselves
+/// via [`.apply_decay()`](MsgBuf::apply_decay).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DecayPolicy {
+    /// The number of consecutive small messages after which the buffer is shrunk.
+    pub after: u32,
+    /// A message is considered small if the capacity of the buffer is at least this many times
+    /// its length.
+    pub factor: usize,
+}
+impl Default for DecayPolicy {
+    /// Shrinks after 64 consecutive messages that are four or more times smaller than the buffer.
+    #[inline]
+    fn default() -> Self {
+        Self { after: 64, factor: 4 }
+    }
+}
+
+/// The run of small messages tracked for the [decay policy](DecayPolicy).
+#[derive(Copy, Clone, Debug, Default)]
+pub(super) struct DecayRun {
+    len: u32,
+    max_msg: usize,
+}
+
+/// Decay.
+impl MsgBuf<'_> {
+    /// Counts the message in the buffer toward its [decay policy](DecayPolicy), shrinking the
+    /// buffer to the size of the largest message of the run once the run is long enough. Does
+    /// nothing if there is no decay policy or no message.
+    pub fn apply_decay(&mut self) {
+        let policy = match self.decay {
+            Some(policy) if self.has_msg => policy,
+            _ => return,
+        };
+        let len = self.fill;
+        if len.saturating_mul(policy.factor) > self.cap {
+            self.decay_run = DecayRun::default();
+            return;
+        }
+        let run = &mut self.decay_run;
+        run.len += 1;
+        run.max_msg = max(run.max_msg, len);
+        if run.len >= policy.after {
+            let max_msg = run.max_msg;
+            self.decay_run = DecayRun::default();
+            self.shrink_to(max_msg);
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use super::{AllocFailed, MsgBuf, OwnedBuf, OwnedBufRawParts};
+use ::bytes::{buf::UninitSlice, BufMut, Bytes, BytesMut};
+use alloc::vec::Vec;
+use core::cmp::{max, min};
+
+/// An [owned buffer](OwnedBuf) made from a [`BytesMut`], to receive into its allocation and hand
+/// it back as a `BytesMut` afterwards.
+///
+/// A `BytesMut` cannot be reconstructed from a base pointer and a capacity, so the allocation is
+/// held as the `Vec` that backs it. Converting from a `BytesMut` that is the sole owner of its
+/// allocation, and back into one, reuses the allocation; a `BytesMut` that shares its allocation
+/// with other `BytesMut`s or `Bytes`, or that has been advanced past its start, has its contents
+/// copied. The contents are considered initialized but not filled.
+#[derive(Debug, Default)]
+pub struct BytesMutBuf(Vec<u8>);
+impl BytesMutBuf {
+    /// Creates an empty buffer, which does not allocate until it grows.
+    #[inline]
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+    /// Converts the buffer back into a `BytesMut` of the initialized part, without copying.
+    #[inline]
+    pub fn into_bytes_mut(self) -> BytesMut {
+        // Goes through the shared representation, which is unique and thus converts without
+        // copying
+        Bytes::from(self.0).into()
+    }
+}
+impl From<BytesMut> for BytesMutBuf {
+    #[inline]
+    fn from(bytes: BytesMut) -> Self {
+        Self(Vec::from(bytes))
+    }
+}
+impl From<BytesMutBuf> for BytesMut {
+    #[inline]
+    fn from(buf: BytesMutBuf) -> Self {
+        buf.into_bytes_mut()
+    }
+}
+
+unsafe impl OwnedBuf for BytesMutBuf {
+    #[inline]
+    unsafe fn from_raw_parts(raw_parts: OwnedBufRawParts) -> Self {
+        Self(unsafe { <Vec<u8> as OwnedBuf>::from_raw_parts(raw_parts) })
+    }
+    #[inline]
+    fn as_raw_parts(&self) -> OwnedBufRawParts {
+        self.0.as_raw_parts()
+    }
+    #[inline]
+    fn grow(&mut self, new_cap: usize) {
+        self.0.grow(new_cap)
+    }
+    #[inline]
+    fn try_grow(&mut self, new_cap: usize) -> Result<(), AllocFailed> {
+        self.0.try_grow(new_cap)
+    }
+    #[inline]
+    fn shrink(&mut self, new_cap: usize) {
+        self.0.shrink(new_cap)
+    }
+}
+
+/// Conversion to [`bytes`] types.
+impl MsgBuf<'_> {
+    /// Takes the filled part of the buffer as a [`BytesMut`], leaving the buffer empty.
+    ///
+    /// If the buffer owns a `Vec` or a [`BytesMutBuf`], the allocation is handed over without
+    /// copying (and refunded to the [pool](super::QuotaPool), if any). Otherwise, the filled part is
+    /// copied into a new allocation, and the buffer keeps its storage.
+    pub fn take_bytes_mut(&mut self) -> BytesMut {
+        let fill = self.fill;
+        if self.own_vt.is::<Vec<u8>>() || self.own_vt.is::<BytesMutBuf>() {
+            if let Some(owned) = self.take_owned() {
+                let mut buf = owned.downcast::<Vec<u8>>().map_or_else(
+                    |owned| owned.downcast::<BytesMutBuf>().unwrap_or_else(|_| unreachable!()),
+                    BytesMutBuf,
+                );
+                buf.0.truncate(fill);
+                return buf.into_bytes_mut();
+            }
+        }
+        let bytes = BytesMut::from(self.filled_part());
+        self.set_fill(0);
+        self.has_msg = false;
+        bytes
+    }
+    /// Takes the filled part of the buffer as [`Bytes`], leaving the buffer empty.
+    ///
+    /// See [`.take_bytes_mut()`](Self::take_bytes_mut) for when this entails a copy.
+    #[inline]
+    pub fn take_bytes(&mut self) -> Bytes {
+        self.take_bytes_mut().freeze()
+    }
+}
+
+/// Wraps the `BytesMut` in a [`BytesMutBuf`], which see for when this entails a copy.
+impl From<BytesMut> for MsgBuf<'_> {
+    #[inline]
+    fn from(bytes: BytesMut) -> Self {
+        Self::from(BytesMutBuf::from(bytes))
+    }
+}
+/// Takes the filled part. See [`.take_bytes_mut()`](MsgBuf::take_bytes_mut).
+impl From<MsgBuf<'_>> for BytesMut {
+    #[inline]
+    fn from(mut buf: MsgBuf<'_>) -> Self {
+        buf.take_bytes_mut()
+    }
+}
+/// Takes the filled part. See [`.take_bytes_mut()`](MsgBuf::take_bytes_mut).
+impl From<MsgBuf<'_>> for Bytes {
+    #[inline]
+    fn from(mut buf: MsgBuf<'_>) -> Self {
+        buf.take_bytes()
+    }
+}
+
+/// Writes to the unfilled part, growing the buffer as needed within the quota and the
+/// [pool](super::QuotaPool), if any.
+///
+/// `.remaining_mut()` accounts for both the quota and the bytes the pool has available at the time
+/// of the call.
+///
+/// ```
+/// # use {bytes::BufMut, recvmsg::MsgBuf};
+/// let mut arr = [0; 4];
+/// let mut buf = MsgBuf::from(arr.as_mut());
+/// buf.put_slice(b"more than four bytes");
+/// assert_eq!(buf.filled_part(), b"more than four bytes");
+/// ```
+unsafe impl BufMut for MsgBuf<'_> {
+    #[inline]
+    fn remaining_mut(&self) -> usize {
+        let mut limit = self.quota.unwrap_or(isize::MAX as usize);
+        if let Some(pool) = &self.pool {
+            // Borrowed buffers spill into an allocation that is charged for in full
+            limit = min(limit, self.owned_cap().saturating_add(pool.available()));
+        }
+        max(limit, self.cap).saturating_sub(self.fill)
+    }
+    #[inline]
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        let new_fill = self.fill + cnt;
+        assert!(new_fill <= self.cap, "attempt to advance past the end of the buffer");
+        // SAFETY: the caller has initialized this much
+        unsafe { self.advance_init_and_set_fill(new_fill) };
+    }
+    /// Grows the buffer if it is full and there is room left to grow.
+    ///
+    /// # Panics
+    /// If growth fails even though `.remaining_mut()` is non-zero, which is only possible if memory
+    /// allocation fails or other buffers attached to the same pool grow in the meantime.
+    #[inline]
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        if self.fill == self.cap && self.remaining_mut() > 0 {
+            if let Err(e) = self.try_grow() {
+                panic!("{e}");
+            }
+        }
+        UninitSlice::uninit(self.unfilled_part())
+    }
+}
@@ -13,6 +13,11 @@ impl Debug for MsgBuf<'_> {
             .field("owned", &self.borrow.is_none())
             .field("own_vt", &self.own_vt)
             .field("quota", quota)
+            .field("oversize", &self.oversize)
+            .field("return_to_borrowed", &self.return_to_borrowed)
+            .field("stash", &self.stash)
+            .field("decay", &self.decay)
+            .field("pool", &self.pool)
             .field("init", &self.init)
             .field("fill", &self.fill)
             .field("has_msg", &self.has_msg)
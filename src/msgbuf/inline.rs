@@ -0,0 +1,183 @@
+use super::{MsgBuf, MuU8};
+use core::{
+    fmt::{self, Debug, Formatter},
+    mem,
+    ops::{Deref, DerefMut},
+    slice,
+};
+
+/// A message reception buffer with `N` bytes of storage inline, which spills to the heap via the
+/// normal growth path when a message doesn't fit there.
+///
+/// Unlike a [`MsgBuf`] created from a stack array, this type has no lifetime parameter, and can
+/// thus be stored in structs and moved around freely. Since a `MsgBuf` pointing to the inline
+/// storage cannot outlive a move, the buffer is accessed through a short-lived
+/// [guard](InlineMsgBufGuard) obtained via [`.get()`](Self::get), which dereferences to `MsgBuf`
+/// and can thus be passed wherever `&mut MsgBuf<'_>` is accepted:
+/// ```
+/// # use recvmsg::msgbuf::InlineMsgBuf;
+/// # fn recv(buf: &mut recvmsg::MsgBuf<'_>) { buf.extend_from_slice(b"hi").unwrap(); }
+/// struct Connection {
+///     buf: InlineMsgBuf<256>,
+/// }
+/// let mut conn = Connection { buf: InlineMsgBuf::new() };
+/// recv(&mut conn.buf.get());
+/// assert_eq!(conn.buf.filled_part(), b"hi");
+/// assert!(!conn.buf.is_spilled());
+/// ```
+///
+/// Settings such as the [quota](MsgBuf::quota) are set through the guard and persist across
+/// guards. Setting [`return_to_borrowed`](MsgBuf::return_to_borrowed) makes the buffer return to
+/// the inline storage once a message fits there again, and
+/// [`.reset_to_borrowed()`](MsgBuf::reset_to_borrowed) can be called on the guard to do so
+/// manually.
+pub struct InlineMsgBuf<const N: usize> {
+    inline: [MuU8; N],
+    state: State,
+}
+/// Everything but the inline storage, so that it can be borrowed separately.
+#[derive(Debug, Default)]
+struct State {
+    /// Settings, along with the heap allocation if the buffer has spilled, in which case the
+    /// cursors below are unused.
+    buf: MsgBuf<'static>,
+    init: usize,
+    fill: usize,
+    has_msg: bool,
+}
+
+impl<const N: usize> InlineMsgBuf<N> {
+    /// Creates an empty buffer with uninitialized inline storage.
+    #[inline]
+    pub fn new() -> Self {
+        Self { inline: [MuU8::uninit(); N], state: State::default() }
+    }
+    /// Returns a guard through which the buffer can be used as a [`MsgBuf`].
+    ///
+    /// Leaking the guard leaks the heap allocation, if there is one, and resets the settings of
+    /// the buffer to their defaults.
+    pub fn get(&mut self) -> InlineMsgBufGuard<'_, N> {
+        let Self { inline, state } = self;
+        let inline_addr = inline.as_ptr() as usize;
+        let mut buf: MsgBuf<'_> = mem::take(&mut state.buf);
+        // Taken rather than copied so that a leaked guard leaves the buffer empty
+        let (init, fill, has_msg) =
+            (mem::take(&mut state.init), mem::take(&mut state.fill), mem::take(&mut state.has_msg));
+        if buf.cap == 0 {
+            buf.put_slice(inline);
+            unsafe {
+                // SAFETY: the guard kept track of this the last time around
+                buf.set_init(init);
+            }
+            buf.set_fill(fill);
+            buf.has_msg = has_msg;
+        } else {
+            buf.stash_slice(inline);
+        }
+        InlineMsgBufGuard { buf, state, inline_addr }
+    }
+
+    /// Returns `true` if the buffer has spilled to the heap.
+    #[inline]
+    pub fn is_spilled(&self) -> bool {
+        self.state.buf.cap > 0
+    }
+    /// Returns the current capacity of the buffer, which is `N` unless it has spilled to the heap.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        if self.is_spilled() {
+            self.state.buf.cap
+        } else {
+            N
+        }
+    }
+    /// Returns the filled part of the buffer.
+    #[inline]
+    pub fn filled_part(&self) -> &[u8] {
+        if self.is_spilled() {
+            self.state.buf.filled_part()
+        } else {
+            // SAFETY: the filled part is initialized
+            unsafe { slice::from_raw_parts(self.inline.as_ptr().cast(), self.state.fill) }
+        }
+    }
+    /// Returns `true` if the buffer contains a received message.
+    #[inline]
+    pub fn has_msg(&self) -> bool {
+        if self.is_spilled() {
+            self.state.buf.has_msg
+        } else {
+            self.state.has_msg
+        }
+    }
+}
+impl<const N: usize> Default for InlineMsgBuf<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<const N: usize> Debug for InlineMsgBuf<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InlineMsgBuf")
+            .field("spilled", &self.is_spilled())
+            .field("capacity", &self.capacity())
+            .field("fill", &self.filled_part().len())
+            .field("has_msg", &self.has_msg())
+            .field("buf", &self.state.buf)
+            .finish()
+    }
+}
+
+/// Guard through which an [`InlineMsgBuf`] is used as a [`MsgBuf`], returned by
+/// [`.get()`](InlineMsgBuf::get).
+///
+/// Puts the state of the buffer back into the `InlineMsgBuf` when dropped. If the `MsgBuf` was
+/// swapped out for one that borrows another slice, its contents are copied to a new heap
+/// allocation (or dropped if the quota doesn't allow for one).
+#[derive(Debug)]
+pub struct InlineMsgBufGuard<'a, const N: usize> {
+    buf: MsgBuf<'a>,
+    state: &'a mut State,
+    /// Used to tell the inline storage apart from other borrowed slices. Not a pointer so as to
+    /// keep the guard `Send`.
+    inline_addr: usize,
+}
+impl<'a, const N: usize> Deref for InlineMsgBufGuard<'a, N> {
+    type Target = MsgBuf<'a>;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.buf
+    }
+}
+impl<const N: usize> DerefMut for InlineMsgBufGuard<'_, N> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.buf
+    }
+}
+impl<const N: usize> Drop for InlineMsgBufGuard<'_, N> {
+    fn drop(&mut self) {
+        let mut buf = mem::take(&mut self.buf);
+        let state = &mut *self.state;
+        let (init, fill, has_msg) = (buf.init, buf.fill, buf.has_msg);
+        if buf.borrow.is_some() && buf.cap > 0 {
+            let slice = buf.take_borrowed().unwrap();
+            if slice.as_ptr() as usize == self.inline_addr {
+                (state.init, state.fill, state.has_msg) = (init, fill, has_msg);
+                state.buf = buf.try_extend_lifetime().expect("buffer still borrowed");
+                return;
+            }
+            let mut owned = buf.try_extend_lifetime().expect("buffer still borrowed");
+            if owned.try_grow_to(fill).is_ok() {
+                // SAFETY: the filled part is initialized
+                let msg = unsafe { slice::from_raw_parts(slice.as_ptr().cast(), fill) };
+                owned.extend_from_slice(msg).expect("grown to fit");
+                owned.has_msg = has_msg;
+            }
+            state.buf = owned;
+        } else {
+            state.buf = buf.try_extend_lifetime().expect("buffer still borrowed");
+        }
+    }
+}
@@ -1,23 +1,56 @@
 use super::{owned::OwnedBuf, owned_default, MsgBuf};
+use core::mem::ManuallyDrop;
 
 /// Lifetime management.
 impl MsgBuf<'_> {
     /// Makes sure `self` is owned by making a new allocation equal in size to the borrowed
     /// capacity if it is borrowed. Discards data in `self` if a reallocation is entailed.
     pub fn make_owned<Owned: OwnedBuf>(self) -> MsgBuf<'static> {
-        self.try_extend_lifetime().unwrap_or_else(|slf| {
+        self.try_extend_lifetime().unwrap_or_else(|mut slf| {
             let mut owned = owned_default::<Owned>();
             owned.grow(slf.cap);
             let mut buf = MsgBuf::from(owned);
             buf.quota = slf.quota;
+            buf.oversize = slf.oversize;
+            buf.decay = slf.decay;
+            buf.return_to_borrowed = slf.return_to_borrowed;
+            if let Some(pool) = slf.pool.take() {
+                // The allocation has already been made, so there's no backing out at this point
+                pool.force_charge(buf.cap);
+                buf.pool = Some(pool);
+            }
             buf
         })
     }
     /// Attempts to extend lifetime to `'static`, failing if the buffer is borrowed.
+    #[allow(clippy::result_large_err)] // Returning the buffer back is the whole point
     pub fn try_extend_lifetime(self) -> Result<MsgBuf<'static>, Self> {
         if self.borrow.is_none() || self.cap == 0 {
-            let Self { ptr, cap, quota, init, borrow: _, own_vt, fill, has_msg } = self;
-            Ok(MsgBuf { ptr, cap, quota, init, borrow: None, own_vt, fill, has_msg })
+            // Must not be dropped, since the returned buffer takes over its allocation
+            let mut slf = ManuallyDrop::new(self);
+            let pool = slf.pool.take();
+            #[rustfmt::skip]
+            let Self {
+                ptr, cap, quota, oversize, decay, decay_run, init, borrow: _, own_vt, fill,
+                has_msg, pool: _, return_to_borrowed, stash: _,
+            } = *slf;
+            Ok(MsgBuf {
+                ptr,
+                cap,
+                quota,
+                oversize,
+                decay,
+                decay_run,
+                init,
+                borrow: None,
+                own_vt,
+                fill,
+                has_msg,
+                pool,
+                return_to_borrowed,
+                // Refers to memory borrowed for the original lifetime
+                stash: None,
+            })
         } else {
             Err(self)
         }
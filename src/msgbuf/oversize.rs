@@ -0,0 +1,25 @@
+/// What the reception helpers do with a message that would make a [`MsgBuf`](super::MsgBuf) grow
+/// beyond its quota.
+///
+/// Set via the `oversize` field of the buffer. For vectored reception, the policy of the last
+/// buffer is used, since that is the one that grows.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum OversizePolicy {
+    /// Leave the message at the front of the queue and return `RecvResult::QuotaExceeded`.
+    ///
+    /// A loop that keeps calling `.recv_msg()` without raising the quota will get the same result
+    /// over and over again.
+    LeaveQueued,
+    /// Take the message off the queue and return `RecvResult::QuotaExceeded`.
+    Discard,
+    /// Grow the buffer to the quota, receive as much of the message as fits, and return
+    /// `RecvResult::Truncated`.
+    Truncate,
+}
+impl Default for OversizePolicy {
+    /// Returns `LeaveQueued`.
+    #[inline]
+    fn default() -> Self {
+        Self::LeaveQueued
+    }
+}
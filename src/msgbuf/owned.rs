@@ -1,8 +1,14 @@
+mod aligned;
 mod grow_fn;
 mod r#impl;
 mod trait_object;
-pub use {grow_fn::*, trait_object::*};
+mod zeroizing;
+#[cfg(all(unix, feature = "std_net"))]
+pub(crate) use zeroizing::move_contents;
+pub(crate) use zeroizing::wipe;
+pub use {aligned::*, grow_fn::*, trait_object::*, zeroizing::ZeroizingBuf};
 
+use super::AllocFailed;
 use core::{mem::ManuallyDrop, ptr::NonNull};
 
 /// Owned buffers for use with [`MsgBuf`](super::MsgBuf).
@@ -17,11 +23,16 @@ use core::{mem::ManuallyDrop, ptr::NonNull};
 /// # Contract
 /// ## Logic
 ///  - For an `OwnedBuf` with init cursor 𝑖, after a call to `.grow()`, the first 𝑖 bytes starting
-///   from the base pointer must match the corresponding values before the call. In other words,
-///   growth must retain the contents of the initialized part.
+///    from the base pointer must match the corresponding values before the call. In other words,
+///    growth must retain the contents of the initialized part.
 /// - The initialization cursor position must not spuriously decrease.
 /// - `.grow()` must increase the buffer's capacity to the given new capacity. It *may* decrease the
 ///   capacity if the new value is lower than the current capacity.
+/// - `.try_grow()` must do the same as `.grow()` when it returns `Ok`, and must leave the buffer
+///   unchanged when it returns `Err`.
+/// - `.shrink()` must not decrease the capacity below the given new capacity. It may decrease the
+///   initialization cursor to the new capacity, but must retain the contents of the initialized
+///   part up to that point.
 ///
 /// ## Safety
 /// - For an `OwnedBuf` with capacity 𝑐, the first 𝑐 bytes starting from the base pointer must be
@@ -32,8 +43,15 @@ use core::{mem::ManuallyDrop, ptr::NonNull};
 ///   deallocate or otherwise invalidate the buffer.
 ///     - The base pointer may only change as a result of `.grow()`. It must not change on any other
 ///       occasion.
-/// - Capacity must not spuriously decrease.
+/// - Capacity must not spuriously decrease; `.shrink()` is the only way it may.
+/// - The base pointer of a buffer with non-zero capacity must be aligned to `ALIGNMENT`.
 pub unsafe trait OwnedBuf: Send + Sync + Sized + 'static {
+    /// The alignment that the base pointer is guaranteed to have whenever the capacity is non-zero,
+    /// including after growth. Must be a power of two.
+    const ALIGNMENT: usize = 1;
+    /// Whether the buffer holds sensitive data, which [`MsgBuf`](super::MsgBuf) is to overwrite
+    /// with zeroes whenever it discards it. See [`ZeroizingBuf`].
+    const SENSITIVE: bool = false;
     /// Creates the owned buffer from its base pointer, capacity and the initialization cursor,
     /// consuming ownership. If `cap` is zero, the buffer is considered empty and the value of `ptr`
     /// is insignificant.
@@ -50,6 +68,22 @@ pub unsafe trait OwnedBuf: Send + Sync + Sized + 'static {
     ///
     /// Does not necessarily have to be able to decrease the buffer's capacity.
     fn grow(&mut self, new_cap: usize);
+    /// Grows the buffer up to the given capacity, reporting allocation failure instead of
+    /// panicking or aborting.
+    ///
+    /// The default implementation calls `.grow()` and thus never fails.
+    #[inline]
+    fn try_grow(&mut self, new_cap: usize) -> Result<(), AllocFailed> {
+        self.grow(new_cap);
+        Ok(())
+    }
+    /// Shrinks the buffer down to the given capacity, or as close to it as possible.
+    ///
+    /// The default implementation does nothing, for buffers that cannot shrink.
+    #[inline]
+    fn shrink(&mut self, new_cap: usize) {
+        let _ = new_cap;
+    }
 }
 
 pub(crate) fn owned_into_raw_parts<T: OwnedBuf>(slf: T) -> OwnedBufRawParts {
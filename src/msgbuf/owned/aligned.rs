@@ -0,0 +1,121 @@
+use super::{super::AllocFailed, OwnedBuf, OwnedBufRawParts};
+use ::alloc::alloc::{alloc, dealloc, handle_alloc_error, realloc, Layout};
+use core::{num::NonZeroUsize, ptr::NonNull};
+
+/// An [owned buffer](OwnedBuf) whose base pointer is aligned to `ALIGN` bytes, for parsing
+/// messages in place as `#[repr(C)]` structures.
+///
+/// `ALIGN` must be a power of two, which is checked at compile time. Growth reallocates with the
+/// same alignment, so the guarantee holds for as long as the buffer lives. See
+/// [`MsgBuf::spill_to()`](crate::MsgBuf::spill_to) for carrying it across the transition from a
+/// borrowed slice to an owned buffer.
+#[derive(Debug)]
+pub struct AlignedBuf<const ALIGN: usize> {
+    ptr: NonNull<u8>,
+    cap: usize,
+    init: usize,
+}
+// SAFETY: owns the allocation
+unsafe impl<const ALIGN: usize> Send for AlignedBuf<ALIGN> {}
+unsafe impl<const ALIGN: usize> Sync for AlignedBuf<ALIGN> {}
+
+impl<const ALIGN: usize> AlignedBuf<ALIGN> {
+    /// Creates an empty buffer, which does not allocate until it grows.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { ptr: Self::dangling(), cap: 0, init: 0 }
+    }
+    /// Creates a buffer with the given capacity.
+    pub fn with_capacity(cap: usize) -> Self {
+        let mut slf = Self::new();
+        slf.grow(cap);
+        slf
+    }
+
+    const fn dangling() -> NonNull<u8> {
+        // SAFETY: powers of two are non-zero
+        unsafe { NonNull::new_unchecked(<Self as OwnedBuf>::ALIGNMENT as *mut u8) }
+    }
+    fn layout(cap: usize) -> Option<Layout> {
+        Layout::from_size_align(cap, <Self as OwnedBuf>::ALIGNMENT).ok()
+    }
+    /// Reallocates to the given non-zero capacity, or allocates if the buffer is empty.
+    /// Fails with `None` if the capacity is too large for a layout.
+    fn reallocate(&mut self, new_cap: usize) -> Result<(), Option<Layout>> {
+        let new_layout = Self::layout(new_cap).ok_or(None)?;
+        let ptr = unsafe {
+            if self.cap == 0 {
+                alloc(new_layout)
+            } else {
+                // SAFETY: the current layout was valid when it was allocated
+                let layout = Layout::from_size_align_unchecked(self.cap, ALIGN);
+                realloc(self.ptr.as_ptr(), layout, new_cap)
+            }
+        };
+        self.ptr = NonNull::new(ptr).ok_or(Some(new_layout))?;
+        self.cap = new_cap;
+        self.init = self.init.min(new_cap);
+        Ok(())
+    }
+}
+impl<const ALIGN: usize> Default for AlignedBuf<ALIGN> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<const ALIGN: usize> Drop for AlignedBuf<ALIGN> {
+    fn drop(&mut self) {
+        if self.cap > 0 {
+            unsafe {
+                // SAFETY: allocated with this layout
+                let layout = Layout::from_size_align_unchecked(self.cap, ALIGN);
+                dealloc(self.ptr.as_ptr(), layout);
+            }
+        }
+    }
+}
+
+unsafe impl<const ALIGN: usize> OwnedBuf for AlignedBuf<ALIGN> {
+    // Every path to the alignment goes through here, which makes an invalid ALIGN fail to compile.
+    const ALIGNMENT: usize = {
+        assert!(ALIGN.is_power_of_two(), "alignment must be a power of two");
+        ALIGN
+    };
+    #[inline]
+    unsafe fn from_raw_parts(OwnedBufRawParts { ptr, cap, init }: OwnedBufRawParts) -> Self {
+        let ptr = if cap == 0 { Self::dangling() } else { ptr };
+        Self { ptr, cap, init }
+    }
+    #[inline]
+    fn as_raw_parts(&self) -> OwnedBufRawParts {
+        OwnedBufRawParts { ptr: self.ptr, cap: self.cap, init: self.init }
+    }
+    fn grow(&mut self, new_cap: usize) {
+        if new_cap > self.cap {
+            match self.reallocate(new_cap) {
+                Ok(()) => {}
+                Err(Some(layout)) => handle_alloc_error(layout),
+                Err(None) => panic!("capacity overflow"),
+            }
+        }
+    }
+    fn try_grow(&mut self, new_cap: usize) -> Result<(), AllocFailed> {
+        let attempted_alloc = match NonZeroUsize::new(new_cap) {
+            Some(a) if new_cap > self.cap => a,
+            _ => return Ok(()),
+        };
+        self.reallocate(new_cap).map_err(|_| AllocFailed { attempted_alloc })
+    }
+    fn shrink(&mut self, new_cap: usize) {
+        if new_cap >= self.cap {
+            return;
+        }
+        if new_cap == 0 {
+            *self = Self::new();
+        } else {
+            // Keeping the larger allocation is fine if this fails
+            let _ = self.reallocate(new_cap);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use super::{OwnedBuf, OwnedBufRawParts};
+use super::{super::AllocFailed, OwnedBuf, OwnedBufRawParts};
 use core::{
     fmt::{self, Debug, Formatter},
     marker::PhantomData,
@@ -13,6 +13,15 @@ pub trait GrowFn: 'static {
     ///
     /// Does not necessarily have to be able to decrease the buffer's capacity.
     fn grow<Owned: OwnedBuf>(owned: &mut Owned, new_cap: usize);
+    /// Grows the buffer up to the given capacity, reporting allocation failure instead of
+    /// panicking or aborting. Must leave the buffer unchanged on failure.
+    ///
+    /// The default implementation calls `grow()` and thus never fails.
+    #[inline]
+    fn try_grow<Owned: OwnedBuf>(owned: &mut Owned, new_cap: usize) -> Result<(), AllocFailed> {
+        Self::grow(owned, new_cap);
+        Ok(())
+    }
 }
 
 /// The default [growth function](GrowFn) of an [owned buffer](OwnedBuf).
@@ -22,6 +31,10 @@ impl GrowFn for DefaultFn {
     fn grow<Owned: OwnedBuf>(owned: &mut Owned, new_cap: usize) {
         owned.grow(new_cap);
     }
+    #[inline]
+    fn try_grow<Owned: OwnedBuf>(owned: &mut Owned, new_cap: usize) -> Result<(), AllocFailed> {
+        owned.try_grow(new_cap)
+    }
 }
 
 /// Applies the given growth function to the given owned buffer type.
@@ -33,6 +46,8 @@ impl<Owned, Gfn> From<Owned> for WithGrowFn<Owned, Gfn> {
     }
 }
 unsafe impl<Owned: OwnedBuf, Gfn: GrowFn> OwnedBuf for WithGrowFn<Owned, Gfn> {
+    const ALIGNMENT: usize = Owned::ALIGNMENT;
+    const SENSITIVE: bool = Owned::SENSITIVE;
     #[inline]
     unsafe fn from_raw_parts(raw_parts: OwnedBufRawParts) -> Self {
         let owned = unsafe { Owned::from_raw_parts(raw_parts) };
@@ -46,6 +61,14 @@ unsafe impl<Owned: OwnedBuf, Gfn: GrowFn> OwnedBuf for WithGrowFn<Owned, Gfn> {
     fn grow(&mut self, new_cap: usize) {
         Gfn::grow(&mut self.0, new_cap);
     }
+    #[inline]
+    fn try_grow(&mut self, new_cap: usize) -> Result<(), AllocFailed> {
+        Gfn::try_grow(&mut self.0, new_cap)
+    }
+    #[inline]
+    fn shrink(&mut self, new_cap: usize) {
+        self.0.shrink(new_cap);
+    }
 }
 impl<Owned: Default, Gfn> Default for WithGrowFn<Owned, Gfn> {
     #[inline]
@@ -1,5 +1,5 @@
-use super::{OwnedBuf, OwnedBufRawParts};
-use core::ptr::NonNull;
+use super::{super::AllocFailed, OwnedBuf, OwnedBufRawParts};
+use core::{num::NonZeroUsize, ptr::NonNull};
 
 unsafe impl OwnedBuf for alloc::vec::Vec<u8> {
     #[inline]
@@ -11,7 +11,7 @@ unsafe impl OwnedBuf for alloc::vec::Vec<u8> {
         OwnedBufRawParts {
             ptr: unsafe {
                 // SAFETY: Vec base is never null
-                NonNull::new_unchecked(self.as_ptr().cast_mut())
+                NonNull::new_unchecked(self.as_ptr() as *mut u8)
             },
             cap: self.capacity(),
             init: self.len(),
@@ -21,6 +21,72 @@ unsafe impl OwnedBuf for alloc::vec::Vec<u8> {
         let incr = new_cap.saturating_sub(self.len());
         self.reserve_exact(incr)
     }
+    fn try_grow(&mut self, new_cap: usize) -> Result<(), AllocFailed> {
+        let attempted_alloc = match NonZeroUsize::new(new_cap) {
+            Some(a) => a,
+            None => return Ok(()),
+        };
+        let incr = new_cap.saturating_sub(self.len());
+        self.try_reserve_exact(incr).map_err(|_| AllocFailed { attempted_alloc })
+    }
+    fn shrink(&mut self, new_cap: usize) {
+        self.truncate(new_cap);
+        self.shrink_to(new_cap);
+    }
+}
+
+/// Only stateless allocators are supported, since the raw parts carry no allocator and it thus has
+/// to be recreated with `Default` whenever the buffer is reconstructed.
+///
+/// A borrowed or default-constructed buffer can be made to allocate through `A` when it spills via
+/// [`.spill_to()`](crate::MsgBuf::spill_to):
+/// ```
+/// # use {allocator_api2::{alloc::Global, vec::Vec}, recvmsg::MsgBuf};
+/// let mut buf = MsgBuf::default();
+/// assert!(buf.spill_to::<Vec<u8, Global>>());
+/// buf.grow_to(64).unwrap();
+/// let owned = buf.take_owned().unwrap();
+/// assert!(owned.is::<Vec<u8, Global>>());
+/// ```
+#[cfg(feature = "allocator_api2")]
+unsafe impl<A> OwnedBuf for allocator_api2::vec::Vec<u8, A>
+where
+    A: allocator_api2::alloc::Allocator + Default + Send + Sync + 'static,
+{
+    #[inline]
+    unsafe fn from_raw_parts(OwnedBufRawParts { ptr, cap, init }: OwnedBufRawParts) -> Self {
+        if cap == 0 {
+            return Self::new_in(A::default());
+        }
+        unsafe { Self::from_raw_parts_in(ptr.as_ptr(), init, cap, A::default()) }
+    }
+    #[inline]
+    fn as_raw_parts(&self) -> OwnedBufRawParts {
+        OwnedBufRawParts {
+            ptr: unsafe {
+                // SAFETY: Vec base is never null
+                NonNull::new_unchecked(self.as_ptr() as *mut u8)
+            },
+            cap: self.capacity(),
+            init: self.len(),
+        }
+    }
+    fn grow(&mut self, new_cap: usize) {
+        let incr = new_cap.saturating_sub(self.len());
+        self.reserve_exact(incr)
+    }
+    fn try_grow(&mut self, new_cap: usize) -> Result<(), AllocFailed> {
+        let attempted_alloc = match NonZeroUsize::new(new_cap) {
+            Some(a) => a,
+            None => return Ok(()),
+        };
+        let incr = new_cap.saturating_sub(self.len());
+        self.try_reserve_exact(incr).map_err(|_| AllocFailed { attempted_alloc })
+    }
+    fn shrink(&mut self, new_cap: usize) {
+        self.truncate(new_cap);
+        self.shrink_to(new_cap);
+    }
 }
 
 unsafe impl OwnedBuf for () {
@@ -33,4 +99,11 @@ unsafe impl OwnedBuf for () {
     fn grow(&mut self, _: usize) {
         panic!("buffer not allowed to allocate")
     }
+    /// Always fails, unless the requested capacity is zero.
+    fn try_grow(&mut self, new_cap: usize) -> Result<(), AllocFailed> {
+        match NonZeroUsize::new(new_cap) {
+            Some(attempted_alloc) => Err(AllocFailed { attempted_alloc }),
+            None => Ok(()),
+        }
+    }
 }
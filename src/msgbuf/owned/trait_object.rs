@@ -1,33 +1,65 @@
 // Déjà vu! I have been in this place before...
 
-// FUTURE downcasting, requires TypeId in const contexts
-
 use super::*;
 use alloc::vec::Vec;
+use core::any::TypeId;
 
 type VtGrow = unsafe fn(OwnedBufRawParts, usize) -> OwnedBufRawParts;
+type VtTryGrow = unsafe fn(OwnedBufRawParts, usize) -> (OwnedBufRawParts, Result<(), AllocFailed>);
+type VtShrink = unsafe fn(OwnedBufRawParts, usize) -> OwnedBufRawParts;
 type VtDrop = unsafe fn(OwnedBufRawParts);
 
 #[derive(Copy, Clone, Debug)]
 pub(crate) struct OwnedBufVtable {
     grow: VtGrow,
+    try_grow: VtTryGrow,
+    shrink: VtShrink,
     drop: VtDrop,
+    // A function because TypeId::of() is not const
+    type_id: fn() -> TypeId,
+    pub(crate) align: usize,
+    pub(crate) sensitive: bool,
 }
 impl OwnedBufVtable {
     // TODO support no-alloc here
     pub(crate) const DEFAULT: &'static Self = Self::new::<Vec<u8>>();
     #[inline]
-    const fn new<T: OwnedBuf>() -> &'static Self {
+    pub(crate) const fn new<T: OwnedBuf>() -> &'static Self {
         unsafe fn vtgrow<T: OwnedBuf>(raw: OwnedBufRawParts, new_cap: usize) -> OwnedBufRawParts {
             let mut slf = unsafe { T::from_raw_parts(raw) };
             slf.grow(new_cap);
             owned_into_raw_parts(slf)
         }
+        unsafe fn vttrygrow<T: OwnedBuf>(
+            raw: OwnedBufRawParts,
+            new_cap: usize,
+        ) -> (OwnedBufRawParts, Result<(), AllocFailed>) {
+            let mut slf = unsafe { T::from_raw_parts(raw) };
+            let rslt = slf.try_grow(new_cap);
+            (owned_into_raw_parts(slf), rslt)
+        }
+        unsafe fn vtshrink<T: OwnedBuf>(raw: OwnedBufRawParts, new_cap: usize) -> OwnedBufRawParts {
+            let mut slf = unsafe { T::from_raw_parts(raw) };
+            slf.shrink(new_cap);
+            owned_into_raw_parts(slf)
+        }
         unsafe fn vtdrop<T: OwnedBuf>(raw: OwnedBufRawParts) {
             drop(unsafe { T::from_raw_parts(raw) });
         }
 
-        &Self { grow: vtgrow::<T>, drop: vtdrop::<T> }
+        &Self {
+            grow: vtgrow::<T>,
+            try_grow: vttrygrow::<T>,
+            shrink: vtshrink::<T>,
+            drop: vtdrop::<T>,
+            type_id: TypeId::of::<T>,
+            align: T::ALIGNMENT,
+            sensitive: T::SENSITIVE,
+        }
+    }
+    #[inline]
+    pub(crate) fn is<T: OwnedBuf>(&self) -> bool {
+        (self.type_id)() == TypeId::of::<T>()
     }
 }
 
@@ -78,6 +110,70 @@ impl DynOwnedBuf {
         );
     }
 
+    /// Grows the buffer up to the given capacity, reporting allocation failure instead of
+    /// panicking or aborting. The buffer is left unchanged on failure.
+    ///
+    /// # Panics
+    /// If the owned buffer implementation successfully returns from `.try_grow()` but fails to
+    /// produce a buffer of the requested capacity.
+    #[inline]
+    pub fn try_grow(&mut self, new_cap: usize) -> Result<(), AllocFailed> {
+        let vt = self.vt;
+        let raw = self.take();
+        let rslt;
+        unsafe {
+            (self.raw, rslt) = (self.vt.try_grow)(raw, new_cap);
+            self.vt = vt;
+        }
+        rslt?;
+        let actual_cap = self.raw.cap;
+        assert!(
+            actual_cap >= new_cap,
+            "growth function error (expected {new_cap} bytes or more, got {actual_cap})"
+        );
+        Ok(())
+    }
+
+    /// Shrinks the buffer down to the given capacity, or as close to it as the underlying type
+    /// allows. Does nothing if the buffer is already that small, or if the type cannot shrink.
+    ///
+    /// The initialized part is retained up to the new capacity.
+    #[inline]
+    pub fn shrink(&mut self, new_cap: usize) {
+        if new_cap >= self.raw.cap {
+            return;
+        }
+        let vt = self.vt;
+        let raw = self.take();
+        unsafe {
+            self.raw = (self.vt.shrink)(raw, new_cap);
+            self.vt = vt;
+        }
+    }
+
+    /// Returns the alignment guaranteed for the base pointer by the underlying type – see
+    /// [`OwnedBuf::ALIGNMENT`].
+    #[inline]
+    pub fn alignment(&self) -> usize {
+        self.vt.align
+    }
+    /// Returns `true` if the underlying type of the buffer is `T`.
+    #[inline]
+    pub fn is<T: OwnedBuf>(&self) -> bool {
+        self.vt.is::<T>()
+    }
+    /// Recovers the buffer as its underlying type, or returns it back if that type is not `T`.
+    #[inline]
+    pub fn downcast<T: OwnedBuf>(self) -> Result<T, Self> {
+        if self.is::<T>() {
+            let (raw, _) = self.into_raw_and_vt();
+            // SAFETY: the raw parts came from a T
+            Ok(unsafe { T::from_raw_parts(raw) })
+        } else {
+            Err(self)
+        }
+    }
+
     /// Relinquishes ownership of the buffer and returns the raw parts, replacing `self` with
     /// an empty buffer of the same underlying type.
     #[inline]
@@ -91,6 +187,11 @@ impl DynOwnedBuf {
         let slf = ManuallyDrop::new(self);
         (slf.raw, slf.vt)
     }
+    /// Creates an empty buffer of the type of the given vtable.
+    pub(crate) fn empty(vt: &'static OwnedBufVtable) -> Self {
+        // SAFETY: the pointer is insignificant for empty buffers of any type
+        Self { vt, raw: OwnedBufRawParts { ptr: NonNull::dangling(), cap: 0, init: 0 } }
+    }
     pub(crate) unsafe fn from_raw_and_vt(
         raw: OwnedBufRawParts,
         vt: &'static OwnedBufVtable,
@@ -0,0 +1,116 @@
+use super::{super::AllocFailed, owned_default, owned_into_raw_parts, OwnedBuf, OwnedBufRawParts};
+use alloc::vec::Vec;
+use core::{
+    cmp::{max, min},
+    fmt::{self, Debug, Formatter},
+    ptr,
+    sync::atomic::{compiler_fence, Ordering::SeqCst},
+};
+
+/// Overwrites the given memory with zeroes in a way that the compiler won't optimize out.
+pub(crate) fn wipe(base: *mut u8, len: usize) {
+    for i in 0..len {
+        // SAFETY: the caller has exclusive access to the memory
+        unsafe { base.add(i).write_volatile(0) };
+    }
+    compiler_fence(SeqCst);
+}
+
+/// Copies the initialized part of `old` into `new`, which must have been grown beforehand, and
+/// returns `new` with its initialization cursor accounting for the copy.
+pub(crate) fn move_contents<Owned: OwnedBuf>(old: &Owned, new: Owned) -> Owned {
+    let old = old.as_raw_parts();
+    let new = owned_into_raw_parts(new);
+    let len = min(old.init, new.cap);
+    unsafe {
+        // SAFETY: both buffers are valid for this much, and they're different allocations
+        ptr::copy_nonoverlapping(old.ptr.as_ptr(), new.ptr.as_ptr(), len);
+        // SAFETY: just initialized the first `len` bytes
+        Owned::from_raw_parts(OwnedBufRawParts { init: max(new.init, len), ..new })
+    }
+}
+
+/// An [owned buffer](OwnedBuf) wrapper for messages containing secrets, such as key material,
+/// which makes sure that no copies of them are left behind in freed memory.
+///
+/// - Growth and shrinking never reallocate in place: a new buffer is allocated, the contents are
+///   copied over, and the old buffer is wiped with zeroes before being dropped.
+/// - The buffer is wiped when dropped.
+/// - [`MsgBuf`](super::super::MsgBuf) wipes the discarded part of the buffer whenever the fill
+///   cursor is lowered, which includes the `.set_fill(0)` invalidation that the reception helpers
+///   perform before receiving a new message. Setting `has_msg` to `false` on its own does not
+///   wipe anything – use [`.wipe()`](super::super::MsgBuf::wipe) for that.
+///
+/// To keep the contents from being swapped out to disk, wrap the inner buffer in `Mlocked` from
+/// `os::unix` (available with the `std_net` feature on Unix).
+pub struct ZeroizingBuf<Owned: OwnedBuf = Vec<u8>>(Owned);
+impl<Owned: OwnedBuf> ZeroizingBuf<Owned> {
+    /// Wraps the given buffer.
+    #[inline]
+    pub fn new(owned: Owned) -> Self {
+        Self(owned)
+    }
+    /// Replaces the inner buffer with `new`, which must have been grown beforehand, copying over
+    /// the contents and wiping the old buffer.
+    fn replace(&mut self, new: Owned) {
+        let new = move_contents(&self.0, new);
+        let old = core::mem::replace(&mut self.0, new);
+        let raw = old.as_raw_parts();
+        wipe(raw.ptr.as_ptr(), raw.cap);
+    }
+}
+impl<Owned: OwnedBuf + Default> Default for ZeroizingBuf<Owned> {
+    #[inline]
+    fn default() -> Self {
+        Self(Owned::default())
+    }
+}
+impl<Owned: OwnedBuf> Debug for ZeroizingBuf<Owned> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("ZeroizingBuf(..)")
+    }
+}
+impl<Owned: OwnedBuf> Drop for ZeroizingBuf<Owned> {
+    fn drop(&mut self) {
+        let raw = self.0.as_raw_parts();
+        wipe(raw.ptr.as_ptr(), raw.cap);
+    }
+}
+
+unsafe impl<Owned: OwnedBuf> OwnedBuf for ZeroizingBuf<Owned> {
+    const ALIGNMENT: usize = Owned::ALIGNMENT;
+    const SENSITIVE: bool = true;
+    #[inline]
+    unsafe fn from_raw_parts(raw_parts: OwnedBufRawParts) -> Self {
+        Self(unsafe { Owned::from_raw_parts(raw_parts) })
+    }
+    #[inline]
+    fn as_raw_parts(&self) -> OwnedBufRawParts {
+        self.0.as_raw_parts()
+    }
+    fn grow(&mut self, new_cap: usize) {
+        if new_cap > self.0.as_raw_parts().cap {
+            let mut new = owned_default::<Owned>();
+            new.grow(new_cap);
+            self.replace(new);
+        }
+    }
+    fn try_grow(&mut self, new_cap: usize) -> Result<(), AllocFailed> {
+        if new_cap > self.0.as_raw_parts().cap {
+            let mut new = owned_default::<Owned>();
+            new.try_grow(new_cap)?;
+            self.replace(new);
+        }
+        Ok(())
+    }
+    /// Moves the contents to a smaller buffer, or keeps the current one if allocating that fails.
+    fn shrink(&mut self, new_cap: usize) {
+        if new_cap >= self.0.as_raw_parts().cap {
+            return;
+        }
+        let mut new = owned_default::<Owned>();
+        if new.try_grow(new_cap).is_ok() {
+            self.replace(new);
+        }
+    }
+}
@@ -7,9 +7,10 @@ use core::{
 /// had to perform a memory allocation.
 #[derive(Copy, Clone, Debug)]
 pub struct QuotaExceeded {
-    /// The quota the buffer had at the time of the error.
+    /// The quota the buffer had at the time of the error, or the limit of its
+    /// [pool](super::QuotaPool) if it was the latter that was exceeded.
     pub quota: usize,
-    /// The size which the buffer was to attain.
+    /// The size which the buffer was to attain, or the total which the pool was to attain.
     pub attempted_alloc: NonZeroUsize,
 }
 impl Display for QuotaExceeded {
@@ -0,0 +1,137 @@
+use super::{MsgBuf, QuotaExceeded};
+use alloc::sync::Arc;
+use core::{
+    num::NonZeroUsize,
+    sync::atomic::{AtomicUsize, Ordering::Relaxed},
+};
+
+/// A memory budget shared by any number of [`MsgBuf`]s, which may be on different threads.
+///
+/// Buffers attached to the pool via [`.attach_to_pool()`](MsgBuf::attach_to_pool) charge the
+/// capacity of their owned allocations to it as they grow, and refund it once the allocation
+/// leaves them – when they are dropped, or via [`.take_owned()`](MsgBuf::take_owned). Borrowed
+/// buffers are not charged for.
+///
+/// Growth that would make the total exceed the pool's limit fails with [`QuotaExceeded`], the
+/// `quota` of which is the limit of the pool and the `attempted_alloc` of which is the total the
+/// pool would have reached. The per-buffer `quota` still applies on top of that.
+///
+/// The handle is reference-counted, and clones of it refer to the same pool.
+#[derive(Clone, Debug)]
+pub struct QuotaPool(Arc<Inner>);
+#[derive(Debug)]
+struct Inner {
+    limit: usize,
+    used: AtomicUsize,
+}
+impl QuotaPool {
+    /// Creates a pool that allows up to `limit` bytes to be held by its buffers in total.
+    #[inline]
+    pub fn new(limit: usize) -> Self {
+        Self(Arc::new(Inner { limit, used: AtomicUsize::new(0) }))
+    }
+    /// Returns the most bytes the buffers attached to the pool may hold in total.
+    #[inline]
+    pub fn limit(&self) -> usize {
+        self.0.limit
+    }
+    /// Returns the number of bytes currently held by the buffers attached to the pool.
+    ///
+    /// May slightly exceed the limit if an allocator provided more memory than it was asked for.
+    #[inline]
+    pub fn used(&self) -> usize {
+        self.0.used.load(Relaxed)
+    }
+    /// Returns the number of bytes that can still be allocated by buffers attached to the pool.
+    #[inline]
+    pub fn available(&self) -> usize {
+        self.limit().saturating_sub(self.used())
+    }
+    /// Returns `true` if both handles refer to the same pool.
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    pub(crate) fn try_charge(&self, amount: usize) -> Result<(), QuotaExceeded> {
+        if amount == 0 {
+            return Ok(());
+        }
+        let limit = self.limit();
+        self.0
+            .used
+            .fetch_update(Relaxed, Relaxed, |used| {
+                used.checked_add(amount).filter(|&total| total <= limit)
+            })
+            .map(|_| ())
+            .map_err(|used| QuotaExceeded {
+                quota: limit,
+                // Nonzero because amount is
+                attempted_alloc: NonZeroUsize::new(used.saturating_add(amount)).unwrap(),
+            })
+    }
+    pub(crate) fn force_charge(&self, amount: usize) {
+        self.0.used.fetch_add(amount, Relaxed);
+    }
+    pub(crate) fn refund(&self, amount: usize) {
+        self.0.used.fetch_sub(amount, Relaxed);
+    }
+}
+
+/// Shared quota pools.
+impl MsgBuf<'_> {
+    /// Attaches the buffer to the given [pool](QuotaPool), charging the capacity of its current
+    /// owned allocation to it. If the buffer was attached to a different pool, it is detached from
+    /// that one first.
+    ///
+    /// Fails without changing anything if the pool can't afford the buffer's current allocation.
+    pub fn attach_to_pool(&mut self, pool: &QuotaPool) -> Result<(), QuotaExceeded> {
+        if self.pool.as_ref().map_or(false, |p| p.ptr_eq(pool)) {
+            return Ok(());
+        }
+        pool.try_charge(self.owned_cap())?;
+        self.detach_from_pool();
+        self.pool = Some(pool.clone());
+        Ok(())
+    }
+    /// Detaches the buffer from the [pool](QuotaPool) it is attached to, if any, refunding the
+    /// capacity of its owned allocation to it.
+    pub fn detach_from_pool(&mut self) -> Option<QuotaPool> {
+        let pool = self.pool.take()?;
+        pool.refund(self.owned_cap());
+        Some(pool)
+    }
+    /// Returns the [pool](QuotaPool) the buffer is attached to, if any.
+    #[inline]
+    pub fn pool(&self) -> Option<&QuotaPool> {
+        self.pool.as_ref()
+    }
+
+    /// The capacity of the owned allocation, which is what the pool is charged for.
+    #[inline]
+    pub(super) fn owned_cap(&self) -> usize {
+        if self.borrow.is_none() {
+            self.cap
+        } else {
+            0
+        }
+    }
+    /// Charges the growth of the owned allocation to `planned` bytes to the pool, settling for
+    /// `target` bytes if the pool can't afford `planned`. Returns the capacity charged for.
+    pub(super) fn charge_pool(
+        &self,
+        target: NonZeroUsize,
+        planned: usize,
+    ) -> Result<usize, QuotaExceeded> {
+        let pool = match &self.pool {
+            Some(pool) => pool,
+            None => return Ok(planned),
+        };
+        let owned = self.owned_cap();
+        if pool.try_charge(planned - owned).is_ok() {
+            return Ok(planned);
+        }
+        pool.try_charge(target.get() - owned)?;
+        Ok(target.get())
+    }
+}
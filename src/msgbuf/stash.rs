@@ -0,0 +1,72 @@
+use super::{DynOwnedBuf, MsgBuf, MuU8};
+use crate::RecvResult;
+use core::{ptr::NonNull, slice};
+
+/// Returning to the borrowed slice.
+impl<'slice> MsgBuf<'slice> {
+    /// Switches back to the borrowed slice the buffer was created from, if it has since moved to an
+    /// owned allocation, returning the latter so that it can be dropped or put in a
+    /// [pool](super::MsgBufPool). The contents of the buffer are discarded.
+    ///
+    /// Returns `None` and does nothing if the buffer is already borrowed, or if it was never
+    /// borrowed in the first place.
+    pub fn reset_to_borrowed(&mut self) -> Option<DynOwnedBuf> {
+        let slice = self.take_stash()?;
+        let owned = self.take_owned();
+        self.put_slice(slice);
+        owned
+    }
+    /// Returns `true` if the buffer has moved from a borrowed slice to an owned allocation, and can
+    /// thus switch back via [`.reset_to_borrowed()`](Self::reset_to_borrowed).
+    #[inline]
+    pub fn can_reset_to_borrowed(&self) -> bool {
+        self.stash.is_some()
+    }
+
+    /// Remembers the borrowed slice of the buffer, which is about to be replaced with an owned
+    /// allocation.
+    pub(super) fn stash_slice(&mut self, slice: &'slice mut [MuU8]) {
+        if slice.is_empty() {
+            return;
+        }
+        let ptr = NonNull::new(slice.as_mut_ptr().cast()).unwrap_or(NonNull::dangling());
+        self.stash = Some((ptr, slice.len()));
+    }
+    fn take_stash(&mut self) -> Option<&'slice mut [MuU8]> {
+        let (ptr, len) = self.stash.take()?;
+        // SAFETY: this is the slice that was borrowed for 'slice and stashed away, and nothing
+        // else refers to it
+        Some(unsafe { slice::from_raw_parts_mut(ptr.as_ptr().cast(), len) })
+    }
+
+    /// Post-processing of a received message done by the reception helpers: returning to the
+    /// borrowed slice if the message fits there and `return_to_borrowed` is set, and
+    /// [decay](super::DecayPolicy). Passes the result through.
+    pub(crate) fn after_recv(&mut self, rslt: RecvResult) -> RecvResult {
+        if let RecvResult::Fit | RecvResult::Spilled | RecvResult::Truncated { .. } = rslt {
+            self.switch_back_to_borrowed();
+            self.apply_decay();
+        }
+        rslt
+    }
+    fn switch_back_to_borrowed(&mut self) {
+        let fill = self.fill;
+        match self.stash {
+            Some((_, len)) if self.return_to_borrowed && fill <= len => {}
+            _ => return,
+        }
+        let slice = self.take_stash().unwrap();
+        for (dst, &src) in slice.iter_mut().zip(self.filled_part()) {
+            *dst = MuU8::new(src);
+        }
+        let has_msg = self.has_msg;
+        drop(self.take_owned());
+        self.put_slice(slice);
+        unsafe {
+            // SAFETY: just copied there
+            self.set_init(fill);
+        }
+        self.set_fill(fill);
+        self.has_msg = has_msg;
+    }
+}
@@ -6,8 +6,19 @@ impl<'slice> MsgBuf<'slice> {
     /// Takes the owned buffer, leaving an empty one in its place. Returns `None` if the buffer is
     /// borrowed, in which case `self` is left untouched. (Zero-sized buffers are considered both
     /// borrowed and owned.)
+    ///
+    /// If the buffer is attached to a [pool](super::QuotaPool), the capacity of the taken buffer is
+    /// refunded to it.
     #[inline]
     pub fn take_owned(&mut self) -> Option<DynOwnedBuf> {
+        let owned = self.take_owned_uncharged()?;
+        if let Some(pool) = &self.pool {
+            pool.refund(owned.as_raw_parts().cap);
+        }
+        Some(owned)
+    }
+    /// Like `.take_owned()`, but leaves the pool alone.
+    pub(super) fn take_owned_uncharged(&mut self) -> Option<DynOwnedBuf> {
         let Self { ptr, cap, init, borrow, .. } = *self;
         if borrow.is_some() && cap > 0 {
             return None;
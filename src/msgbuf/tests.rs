@@ -1,5 +1,5 @@
-use super::MsgBuf;
-use alloc::vec::Vec;
+use super::{DecayPolicy, GrowError, InlineMsgBuf, MsgBuf, MsgBufPool, QuotaPool};
+use alloc::{boxed::Box, vec::Vec};
 use core::mem::MaybeUninit;
 
 #[test]
@@ -70,3 +70,492 @@ fn drop() {
         let _ = MsgBuf::with_capacity::<Vec<u8>>(1024 * 1024 * 32);
     }
 }
+
+#[test]
+fn try_grow_failure() {
+    let mut bufbak = [0; REF.len()];
+    bufbak.copy_from_slice(REF);
+    let mut buf = MsgBuf::from(&mut bufbak[..]);
+    buf.set_fill(REF.len());
+    buf.has_msg = true;
+    assert!(matches!(buf.try_grow_to(isize::MAX as usize), Err(GrowError::AllocFailed(..))));
+    assert_eq!(buf.capacity(), REF.len());
+    assert_eq!(buf.filled_part(), REF);
+    assert!(buf.has_msg);
+    retain_check(&mut buf);
+
+    let mut buf = MsgBuf::from(Vec::from(REF));
+    buf.set_fill(REF.len());
+    assert!(matches!(buf.try_grow_to(isize::MAX as usize), Err(GrowError::AllocFailed(..))));
+    assert_eq!(buf.filled_part(), REF);
+    retain_check(&mut buf);
+
+    let mut buf = MsgBuf::from(());
+    assert!(matches!(buf.try_grow(), Err(GrowError::AllocFailed(..))));
+    buf.quota = Some(0);
+    assert!(matches!(buf.try_grow(), Err(GrowError::QuotaExceeded(..))));
+}
+
+#[test]
+fn quota_pool() {
+    let pool = QuotaPool::new(1024);
+    let mut buf1 = MsgBuf::from(Vec::new());
+    let mut buf2 = MsgBuf::from(Vec::new());
+    buf1.attach_to_pool(&pool).unwrap();
+    buf2.attach_to_pool(&pool).unwrap();
+
+    buf1.grow_to(600).unwrap();
+    assert_eq!(pool.used(), buf1.capacity());
+    let qe = buf2.grow_to(600).unwrap_err();
+    assert_eq!(qe.quota, 1024);
+    assert!(qe.attempted_alloc.get() > 1024);
+    assert_eq!(buf2.capacity(), 0);
+
+    // Borrowed buffers are free, but growing them isn't.
+    let mut bufbak = [0; 16];
+    let mut buf3 = MsgBuf::from(&mut bufbak[..]);
+    buf3.attach_to_pool(&pool).unwrap();
+    assert_eq!(pool.used(), buf1.capacity());
+    assert!(buf3.grow_to(1000).is_err());
+
+    let owned = buf1.take_owned().unwrap();
+    assert_eq!(pool.used(), 0);
+    core::mem::drop(owned);
+    buf2.grow_to(600).unwrap();
+    let buf2 = buf2.try_extend_lifetime().unwrap();
+    assert_eq!(pool.used(), buf2.capacity());
+    core::mem::drop(buf2);
+    assert_eq!(pool.used(), 0);
+
+    buf1.grow_to(100).unwrap();
+    let used = pool.used();
+    assert!(used >= 100);
+    assert!(buf1.detach_from_pool().is_some());
+    assert_eq!(pool.used(), 0);
+    let small = QuotaPool::new(used - 1);
+    assert!(buf1.attach_to_pool(&small).is_err());
+    assert!(buf1.pool().is_none());
+}
+
+#[test]
+fn buf_pool() {
+    let mut pool = MsgBufPool::with_quota(4096, Some(2048));
+    let mut buf = pool.get(100).unwrap();
+    assert!(buf.capacity() >= 100);
+    assert_eq!(buf.quota, Some(2048));
+    buf.extend_from_slice(REF).unwrap();
+    let ptr = buf.as_ptr();
+    let cap = buf.capacity();
+    assert!(pool.recycle(buf));
+    assert_eq!(pool.retained(), cap);
+
+    // Taken out again instead of allocating.
+    let buf = pool.get(50).unwrap();
+    assert_eq!(buf.as_ptr(), ptr);
+    assert_eq!(buf.len_filled(), 0);
+    assert_eq!(pool.retained(), 0);
+
+    // The smallest one that fits is chosen.
+    let big = pool.get(1000).unwrap();
+    let big_cap = big.capacity();
+    assert!(pool.recycle(big));
+    assert!(pool.recycle(buf));
+    assert_eq!(pool.get(1).unwrap().as_ptr(), ptr);
+    assert_eq!(pool.get(1).unwrap().capacity(), big_cap);
+
+    let retained = pool.retained();
+    assert!(pool.get(4096).is_err());
+    assert_eq!(pool.retained(), retained);
+    let mut oversized = MsgBuf::from(Vec::new());
+    oversized.grow_to(3000).unwrap();
+    assert!(!pool.recycle(oversized));
+    pool.set_quota(None);
+    let grown = || {
+        let mut buf = MsgBuf::from(Vec::new());
+        buf.grow_to(3000).unwrap();
+        buf.take_owned().unwrap()
+    };
+    assert!(pool.put(grown()));
+    assert!(!pool.put(grown())); // Would exceed the bound
+    assert!(pool.retained() <= pool.max_retained());
+    pool.set_quota(Some(2048));
+    assert_eq!(pool.retained(), 0);
+}
+
+#[test]
+fn shrink() {
+    let mut buf = MsgBuf::from(Vec::with_capacity(4096));
+    buf.extend_from_slice(REF).unwrap();
+    buf.shrink_to(1024);
+    assert!(buf.capacity() < 4096);
+    assert!(buf.capacity() >= 1024);
+    buf.shrink_to_fit();
+    assert!(buf.capacity() < 1024);
+    assert_eq!(buf.filled_part(), REF);
+
+    let mut bufbak = [0; 64];
+    let mut buf = MsgBuf::from(&mut bufbak[..]);
+    buf.shrink_to_fit();
+    assert_eq!(buf.capacity(), 64);
+
+    let pool = QuotaPool::new(8192);
+    let mut buf = MsgBuf::from(Vec::new());
+    buf.attach_to_pool(&pool).unwrap();
+    buf.grow_to(4096).unwrap();
+    buf.shrink_to(16);
+    assert_eq!(pool.used(), buf.capacity());
+}
+
+#[test]
+fn decay() {
+    let mut buf = MsgBuf::from(Vec::with_capacity(4096));
+    let cap = buf.capacity();
+    buf.decay = Some(DecayPolicy { after: 3, factor: 4 });
+    let recv = |buf: &mut MsgBuf<'_>, msg: &[u8]| {
+        buf.set_fill(0);
+        buf.extend_from_slice(msg).unwrap();
+        buf.has_msg = true;
+        buf.apply_decay();
+    };
+    recv(&mut buf, &REF[..10]);
+    recv(&mut buf, &REF[..20]);
+    // A large message breaks the run.
+    recv(&mut buf, &[0; 2048]);
+    recv(&mut buf, &REF[..10]);
+    recv(&mut buf, &REF[..20]);
+    assert_eq!(buf.capacity(), cap);
+    recv(&mut buf, &REF[..15]);
+    assert!(buf.capacity() < cap);
+    assert!(buf.capacity() >= 20);
+    assert_eq!(buf.filled_part(), &REF[..15]);
+}
+
+#[test]
+fn reset_to_borrowed() {
+    let mut slice = [MaybeUninit::new(0); 32];
+    let ptr = slice.as_ptr().cast::<u8>();
+    let mut buf = MsgBuf::from(slice.as_mut());
+    assert!(!buf.can_reset_to_borrowed());
+    assert!(buf.reset_to_borrowed().is_none());
+
+    buf.extend_from_slice(&REF[..16]).unwrap();
+    buf.grow_to(64).unwrap();
+    assert_ne!(buf.as_ptr(), ptr);
+    assert!(buf.can_reset_to_borrowed());
+    let owned = buf.reset_to_borrowed().expect("no owned buffer returned");
+    assert!(owned.as_raw_parts().cap >= 64);
+    assert_eq!(buf.as_ptr(), ptr);
+    assert_eq!(buf.capacity(), 32);
+    assert!(!buf.has_msg);
+
+    // Automatic return after a small message
+    buf.return_to_borrowed = true;
+    buf.grow_to(64).unwrap();
+    buf.extend_from_slice(&REF[..40]).unwrap();
+    buf.has_msg = true;
+    buf.after_recv(crate::RecvResult::Spilled);
+    assert_ne!(buf.as_ptr(), ptr);
+    buf.set_fill(0);
+    buf.extend_from_slice(&REF[..20]).unwrap();
+    buf.after_recv(crate::RecvResult::Fit);
+    assert_eq!(buf.as_ptr(), ptr);
+    assert_eq!(buf.filled_part(), &REF[..20]);
+    assert!(buf.has_msg);
+    assert!(!buf.can_reset_to_borrowed());
+}
+
+#[test]
+fn inline() {
+    let mut buf = InlineMsgBuf::<16>::new();
+    {
+        let mut guard = buf.get();
+        guard.quota = Some(64);
+        guard.extend_from_slice(&REF[..10]).unwrap();
+        guard.has_msg = true;
+    }
+    // Moving the buffer must not invalidate its contents.
+    let mut buf = Box::new(buf);
+    assert!(!buf.is_spilled());
+    assert_eq!(buf.filled_part(), &REF[..10]);
+    assert!(buf.has_msg());
+
+    {
+        let mut guard = buf.get();
+        assert_eq!(guard.filled_part(), &REF[..10]);
+        guard.grow_to(32).unwrap();
+        guard.extend_from_slice(&REF[10..30]).unwrap();
+    }
+    assert!(buf.is_spilled());
+    assert_eq!(buf.filled_part(), &REF[..30]);
+    assert_eq!(buf.get().quota, Some(64));
+
+    // Back to the inline storage.
+    assert!(buf.get().reset_to_borrowed().is_some());
+    assert!(!buf.is_spilled());
+    assert_eq!(buf.capacity(), 16);
+
+    // A foreign slice swapped in gets copied out.
+    let mut other = [0; 8];
+    other[..4].copy_from_slice(&REF[..4]);
+    {
+        let mut guard = buf.get();
+        let mut foreign = MsgBuf::from(other.as_mut());
+        foreign.set_fill(4);
+        core::mem::swap(&mut *guard, &mut foreign);
+    }
+    assert!(buf.is_spilled());
+    assert_eq!(buf.filled_part(), &REF[..4]);
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn bytes() {
+    use super::BytesMutBuf;
+    use bytes::{BufMut, Bytes, BytesMut};
+
+    let mut buf = MsgBuf::from(Vec::with_capacity(64));
+    buf.extend_from_slice(&REF[..20]).unwrap();
+    let ptr = buf.as_ptr();
+    let bm = buf.take_bytes_mut();
+    assert_eq!(&bm[..], &REF[..20]);
+    assert_eq!(bm.as_ptr(), ptr, "owned Vec was copied");
+    assert_eq!(buf.capacity(), 0);
+
+    let mut buf = MsgBuf::from(bm);
+    assert_eq!(buf.as_ptr(), ptr, "BytesMut was copied");
+    assert_eq!(buf.len_init(), 20);
+
+    // Borrowed buffers are copied and keep their storage.
+    let mut arr = [0; 8];
+    let mut buf2 = MsgBuf::from(arr.as_mut());
+    buf2.extend_from_slice(&REF[..4]).unwrap();
+    assert_eq!(Bytes::from(buf2), &REF[..4]);
+
+    buf.set_fill(0);
+    buf.quota = Some(128);
+    assert_eq!(buf.remaining_mut(), 128);
+    BufMut::put_slice(&mut buf, &[0x55; 100]);
+    BufMut::put_u8(&mut buf, 0xff);
+    assert_eq!(&buf.filled_part()[..100], &[0x55; 100]);
+    assert_eq!(buf.filled_part()[100], 0xff);
+    assert_eq!(buf.remaining_mut(), 27);
+    assert_eq!(BytesMut::from(buf).len(), 101);
+
+    // Received into the allocation of a BytesMut, which is handed back without copying.
+    let mut buf = MsgBuf::from(BytesMut::with_capacity(32));
+    let ptr = buf.as_ptr();
+    buf.extend_from_slice(&REF[..16]).unwrap();
+    assert_eq!(buf.as_ptr(), ptr);
+    buf.grow_to(1000).unwrap();
+    let ptr = buf.as_ptr();
+    let bm = buf.take_bytes_mut();
+    assert_eq!(bm.as_ptr(), ptr, "BytesMutBuf was copied");
+    assert_eq!(&bm[..], &REF[..16]);
+    assert_eq!(BytesMutBuf::from(bm).into_bytes_mut().as_ptr(), ptr);
+
+    // Writes are limited to what the pool can afford
+    let pool = QuotaPool::new(8);
+    let mut buf = MsgBuf::default();
+    buf.attach_to_pool(&pool).unwrap();
+    assert_eq!(buf.remaining_mut(), 8);
+    BufMut::put_slice(&mut buf, &REF[..8]);
+    assert_eq!(buf.filled_part(), &REF[..8]);
+    assert_eq!(buf.remaining_mut(), 0);
+    assert_eq!(pool.available(), 0);
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+#[should_panic]
+fn bytes_pool_exhausted() {
+    use bytes::BufMut;
+
+    let pool = QuotaPool::new(8);
+    let mut buf = MsgBuf::default();
+    buf.attach_to_pool(&pool).unwrap();
+    // Panics instead of looping forever
+    BufMut::put_slice(&mut buf, b"more than eight bytes here");
+}
+
+#[cfg(all(feature = "std_net", any(target_os = "linux", target_os = "android")))]
+#[test]
+fn mmap() {
+    use super::{DefaultFn, OwnedBuf, WithGrowFn};
+    use crate::os::unix::MmapBuf;
+
+    let mut owned = MmapBuf::with_capacity(1);
+    let raw = owned.as_raw_parts();
+    assert!(raw.cap >= 1);
+    assert_eq!(raw.init, raw.cap);
+    unsafe { raw.ptr.as_ptr().copy_from_nonoverlapping(REF.as_ptr(), REF.len()) };
+
+    // Large enough to force the mapping to move.
+    owned.grow(64 << 20);
+    let grown = owned.as_raw_parts();
+    assert!(grown.cap >= 64 << 20);
+    assert!(grown.init >= raw.init);
+    let head = unsafe { core::slice::from_raw_parts(grown.ptr.as_ptr(), REF.len()) };
+    assert_eq!(head, REF);
+
+    owned.shrink(1);
+    assert_eq!(owned.as_raw_parts().cap, raw.cap);
+    owned.shrink(0);
+    assert_eq!(owned.as_raw_parts().cap, 0);
+
+    let mut buf = MsgBuf::new_owned(WithGrowFn::<MmapBuf, DefaultFn>::default());
+    buf.extend_from_slice(REF).unwrap();
+    buf.grow_to(1 << 20).unwrap();
+    assert_eq!(buf.filled_part(), REF);
+    assert_eq!(buf.len_init(), buf.capacity());
+}
+
+#[test]
+fn aligned() {
+    use super::{AlignedBuf, OwnedBuf};
+
+    let mut owned = AlignedBuf::<64>::with_capacity(10);
+    assert_eq!(owned.as_raw_parts().ptr.as_ptr() as usize % 64, 0);
+    owned.grow(4096);
+    assert_eq!(owned.as_raw_parts().ptr.as_ptr() as usize % 64, 0);
+    let mut buf = MsgBuf::from(owned);
+    assert_eq!(buf.alignment(), 64);
+    buf.extend_from_slice(REF).unwrap();
+    buf.shrink_to_fit();
+    assert_eq!(buf.filled_part(), REF);
+    assert_eq!(buf.as_ptr() as usize % 64, 0);
+
+    // Spilling from a misaligned slice
+    let mut arr = [MaybeUninit::new(0); 17];
+    let slice = if arr.as_ptr() as usize % 2 == 0 { &mut arr[1..] } else { &mut arr[..16] };
+    let mut buf = MsgBuf::from(slice);
+    assert_eq!(buf.alignment(), 1);
+    assert!(buf.spill_to::<AlignedBuf<16>>());
+    assert_eq!(buf.alignment(), 1);
+    buf.extend_from_slice(&REF[..16]).unwrap();
+    buf.grow_to(64).unwrap();
+    assert_eq!(buf.as_ptr() as usize % 16, 0);
+    assert_eq!(buf.filled_part(), &REF[..16]);
+    // Still 1 because of the stashed slice
+    assert_eq!(buf.alignment(), 1);
+    assert!(!buf.spill_to::<Vec<u8>>());
+    let _ = buf.reset_to_borrowed();
+    let _ = buf.take_borrowed();
+    assert_eq!(buf.alignment(), 16);
+}
+
+#[test]
+fn zeroizing() {
+    use super::{AllocFailed, OwnedBuf, OwnedBufRawParts, ZeroizingBuf};
+
+    /// Checks that the whole allocation has been wiped by the time it's dropped.
+    #[derive(Default)]
+    struct Checked(Vec<u8>);
+    impl Drop for Checked {
+        fn drop(&mut self) {
+            let raw = self.0.as_raw_parts();
+            let all = unsafe { core::slice::from_raw_parts(raw.ptr.as_ptr(), raw.init) };
+            assert!(all.iter().all(|&b| b == 0), "dropped without wiping");
+        }
+    }
+    unsafe impl OwnedBuf for Checked {
+        unsafe fn from_raw_parts(raw_parts: OwnedBufRawParts) -> Self {
+            Self(unsafe { <Vec<u8> as OwnedBuf>::from_raw_parts(raw_parts) })
+        }
+        fn as_raw_parts(&self) -> OwnedBufRawParts {
+            self.0.as_raw_parts()
+        }
+        fn grow(&mut self, new_cap: usize) {
+            self.0.grow(new_cap);
+        }
+        fn try_grow(&mut self, new_cap: usize) -> Result<(), AllocFailed> {
+            self.0.try_grow(new_cap)
+        }
+        fn shrink(&mut self, new_cap: usize) {
+            self.0.shrink(new_cap);
+        }
+    }
+
+    let mut buf = MsgBuf::from(ZeroizingBuf::<Checked>::default());
+    buf.extend_from_slice(REF).unwrap();
+    buf.grow_to(4096).unwrap();
+    assert_eq!(buf.filled_part(), REF);
+    buf.shrink_to_fit();
+    assert_eq!(buf.filled_part(), REF);
+    buf.set_fill(10);
+    assert_eq!(buf.filled_part(), &REF[..10]);
+    assert!(buf.init_part()[10..].iter().all(|&b| b == 0));
+    buf.set_fill(0);
+    assert!(buf.init_part().iter().all(|&b| b == 0));
+    buf.extend_from_slice(REF).unwrap();
+    core::mem::drop(buf);
+
+    // The borrowed slice is wiped after spilling
+    let mut arr = [MaybeUninit::new(0); 16];
+    let mut buf = MsgBuf::from(arr.as_mut());
+    assert!(buf.spill_to::<ZeroizingBuf<Checked>>());
+    buf.extend_from_slice(REF).unwrap();
+    assert_eq!(buf.filled_part(), REF);
+    core::mem::drop(buf);
+    assert!(arr.iter().all(|b| unsafe { b.assume_init() } == 0));
+
+    // Explicit wiping works with any buffer type
+    let mut buf = MsgBuf::from(Vec::new());
+    buf.extend_from_slice(REF).unwrap();
+    buf.has_msg = true;
+    buf.wipe();
+    assert!(!buf.has_msg);
+    assert_eq!(buf.len_filled(), 0);
+    assert!(buf.init_part().iter().all(|&b| b == 0));
+}
+
+#[cfg(all(feature = "std_net", unix))]
+#[test]
+fn mlock() {
+    use super::ZeroizingBuf;
+    use crate::os::unix::Mlocked;
+
+    let mut buf = MsgBuf::from(ZeroizingBuf::<Mlocked>::default());
+    buf.extend_from_slice(REF).unwrap();
+    buf.grow_to(1024).unwrap();
+    assert_eq!(buf.filled_part(), REF);
+    buf.shrink_to_fit();
+    assert_eq!(buf.filled_part(), REF);
+}
+
+#[cfg(feature = "allocator_api2")]
+#[test]
+fn allocator_api2() {
+    use allocator_api2::alloc::{AllocError, Allocator, Global, Layout};
+    use core::{
+        ptr::NonNull,
+        sync::atomic::{AtomicUsize, Ordering::Relaxed},
+    };
+
+    static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+    #[derive(Default)]
+    struct Counting;
+    unsafe impl Allocator for Counting {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            ALLOCATED.fetch_add(layout.size(), Relaxed);
+            Global.allocate(layout)
+        }
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            ALLOCATED.fetch_sub(layout.size(), Relaxed);
+            unsafe { Global.deallocate(ptr, layout) }
+        }
+    }
+    type CountingVec = allocator_api2::vec::Vec<u8, Counting>;
+
+    let mut arr = [MaybeUninit::new(0); 8];
+    let mut buf = MsgBuf::from(arr.as_mut());
+    assert!(buf.spill_to::<CountingVec>());
+    buf.extend_from_slice(&REF[..8]).unwrap();
+    buf.grow_to(100).unwrap();
+    assert_eq!(ALLOCATED.load(Relaxed), buf.capacity());
+    buf.extend_from_slice(&REF[8..]).unwrap();
+    assert_eq!(buf.filled_part(), REF);
+    buf.shrink_to_fit();
+    assert_eq!(ALLOCATED.load(Relaxed), buf.capacity());
+    core::mem::drop(buf);
+    assert_eq!(ALLOCATED.load(Relaxed), 0);
+}
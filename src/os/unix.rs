@@ -1,8 +1,39 @@
 #![allow(unsafe_code)]
 
+mod cmsg;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod cmsg_value;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod cred;
+#[cfg(target_os = "linux")]
+mod memfd;
+mod mlock;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod mmap;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod pktinfo;
+pub mod seqpacket;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod timestamp;
+mod with_cmsg;
+#[cfg(target_os = "linux")]
+pub use memfd::*;
+pub use {
+    cmsg::*,
+    mlock::*,
+    seqpacket::{UnixSeqpacket, UnixSeqpacketListener},
+    with_cmsg::*,
+};
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub use {cmsg_value::RecvWithCmsgValue, cred::*, mmap::*, pktinfo::*, timestamp::*};
+
 use crate::MsgBuf;
-use core::{cmp::min, mem::size_of_val};
-use libc::{iovec, msghdr, recvmsg, sockaddr_storage, MSG_TRUNC};
+use alloc::vec::Vec;
+use core::{cmp::min, mem::size_of_val, ptr, slice};
+use libc::{
+    c_int, getsockopt, iovec, msghdr, recvmsg, setsockopt, sockaddr_storage, socklen_t, MSG_TRUNC,
+    SOCK_DGRAM, SOL_SOCKET, SO_TYPE,
+};
 use std::{
     io,
     os::fd::{AsRawFd, BorrowedFd},
@@ -13,6 +44,9 @@ use std::{
 /// `hdr` is to `msg_iov` and `msg_iovlen`, in addition to the system's doings.) The return value of
 /// the `recvmsg()` call is additionally returned for convenience.
 ///
+/// Receiving zero bytes is only reported as end of stream (`None`) on sockets that aren't of the
+/// `SOCK_DGRAM` type – on datagram sockets, it means that an empty message has been received.
+///
 /// # Safety
 /// Pointers in `hdr`, save for `msg_iov` and `msg_iovlen`, must be valid from the system's
 /// perspective.
@@ -23,13 +57,39 @@ pub unsafe fn recv_trunc_recvmsg_with_msghdr(
     abuf: Option<&mut sockaddr_storage>,
     flags: i32,
 ) -> io::Result<(Option<bool>, usize)> {
-    buf.set_fill(0);
-    buf.has_msg = false;
+    unsafe { recv_trunc_recvmsg_vectored_with_msghdr(fd, hdr, slice::from_mut(buf), abuf, flags) }
+}
 
-    let out = &mut buf[..];
-    let mut iov = iovec { iov_base: out.as_mut_ptr().cast(), iov_len: out.len() };
-    hdr.msg_iov = &mut iov;
-    hdr.msg_iovlen = 1;
+/// The number of buffers a vectored receive can be given before the `iovec`s for them have to be
+/// allocated on the heap.
+const STACK_IOVS: usize = 8;
+
+/// Like [`recv_trunc_recvmsg_with_msghdr()`], but scatters the message across `bufs`, as per
+/// [`.recv_trunc_vectored()`](crate::TruncatingRecvMsgVectored::recv_trunc_vectored).
+///
+/// # Safety
+/// See [`recv_trunc_recvmsg_with_msghdr()`].
+pub unsafe fn recv_trunc_recvmsg_vectored_with_msghdr(
+    fd: BorrowedFd,
+    hdr: &mut msghdr,
+    bufs: &mut [MsgBuf<'_>],
+    abuf: Option<&mut sockaddr_storage>,
+    flags: i32,
+) -> io::Result<(Option<bool>, usize)> {
+    let mut stack_iovs;
+    let mut heap_iovs;
+    let iovs = if bufs.len() <= STACK_IOVS {
+        stack_iovs = [iovec { iov_base: ptr::null_mut(), iov_len: 0 }; STACK_IOVS];
+        for (iov, buf) in stack_iovs.iter_mut().zip(bufs.iter_mut()) {
+            *iov = iovec_for(buf);
+        }
+        &mut stack_iovs[..bufs.len()]
+    } else {
+        heap_iovs = bufs.iter_mut().map(iovec_for).collect::<Vec<_>>();
+        &mut heap_iovs[..]
+    };
+    hdr.msg_iov = iovs.as_mut_ptr();
+    hdr.msg_iovlen = iovs.len() as _;
 
     if let Some(abuf) = abuf {
         hdr.msg_name = (abuf as *mut sockaddr_storage).cast();
@@ -37,7 +97,7 @@ pub unsafe fn recv_trunc_recvmsg_with_msghdr(
     }
 
     let bytes_recved = unsafe {
-        // SAFETY: msghdr is zero-initialized except for the validly initialized iovec
+        // SAFETY: msghdr is zero-initialized except for the validly initialized iovecs
         recvmsg(fd.as_raw_fd(), hdr, flags)
     };
     let bytes_recved = if bytes_recved < 0 {
@@ -45,14 +105,18 @@ pub unsafe fn recv_trunc_recvmsg_with_msghdr(
     } else {
         bytes_recved as usize
     };
-    // For MSG_TRUNC on Linux
-    let bytes_recved_to_set = min(bytes_recved, buf.capacity());
 
-    unsafe { buf.advance_init_and_set_fill(bytes_recved_to_set) };
+    let mut remaining = bytes_recved;
+    for buf in bufs.iter_mut() {
+        // For MSG_TRUNC on Linux
+        let bytes_recved_to_set = min(remaining, buf.capacity());
+        unsafe { buf.advance_init_and_set_fill(bytes_recved_to_set) };
+        remaining -= bytes_recved_to_set;
+    }
 
     Ok((
-        if bytes_recved > 0 {
-            buf.has_msg = true;
+        if bytes_recved > 0 || is_datagram_socket(fd) {
+            bufs.iter_mut().for_each(|buf| buf.has_msg = true);
             let fit = hdr.msg_flags & MSG_TRUNC == 0;
             Some(fit)
         } else {
@@ -61,3 +125,56 @@ pub unsafe fn recv_trunc_recvmsg_with_msghdr(
         bytes_recved,
     ))
 }
+
+/// Checks whether receiving zero bytes from the socket means that an empty message has been
+/// received, as opposed to end of stream.
+///
+/// If the socket type can't be queried, the message that has already been received is reported as
+/// such rather than lost to an error.
+fn is_datagram_socket(fd: BorrowedFd<'_>) -> bool {
+    getsockopt_int(fd, SOL_SOCKET, SO_TYPE).map_or(true, |ty| ty == SOCK_DGRAM)
+}
+
+fn iovec_for(buf: &mut MsgBuf<'_>) -> iovec {
+    buf.set_fill(0);
+    buf.has_msg = false;
+    let out = &mut buf[..];
+    iovec { iov_base: out.as_mut_ptr().cast(), iov_len: out.len() }
+}
+
+/// Sets an integer (or boolean) socket option.
+#[allow(dead_code)]
+pub(crate) fn setsockopt_int(
+    fd: BorrowedFd<'_>,
+    level: c_int,
+    name: c_int,
+    val: c_int,
+) -> io::Result<()> {
+    let ret = unsafe {
+        setsockopt(
+            fd.as_raw_fd(),
+            level,
+            name,
+            (&val as *const c_int).cast(),
+            size_of_val(&val) as _,
+        )
+    };
+    if ret == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+/// Gets an integer (or boolean) socket option.
+pub(crate) fn getsockopt_int(fd: BorrowedFd<'_>, level: c_int, name: c_int) -> io::Result<c_int> {
+    let mut val: c_int = 0;
+    let mut len = size_of_val(&val) as socklen_t;
+    let ret = unsafe {
+        getsockopt(fd.as_raw_fd(), level, name, (&mut val as *mut c_int).cast(), &mut len)
+    };
+    if ret == 0 {
+        Ok(val)
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
@@ -0,0 +1,351 @@
+use crate::{GrowError, MsgBuf, QuotaExceeded};
+use alloc::vec::Vec;
+use core::{
+    cmp::min,
+    fmt::{self, Debug, Formatter},
+    mem::{align_of, size_of, take, MaybeUninit},
+    ptr,
+};
+use libc::{c_int, c_void, cmsghdr, CMSG_LEN, CMSG_SPACE, SCM_RIGHTS, SOL_SOCKET};
+use std::os::fd::{FromRawFd, OwnedFd};
+
+/// A buffer for receiving ancillary data (control messages) alongside messages.
+///
+/// Wraps a [`MsgBuf`], inheriting its ability to transition from a borrowed buffer to an owned
+/// one, as well as its quota. The control messages are stored at the first suitably aligned
+/// position within the wrapped buffer, which means that a few bytes of its capacity may go unused.
+///
+/// File descriptors received via `SCM_RIGHTS` are owned by the buffer until they are taken out with
+/// [`.take_fds()`](Self::take_fds): those that are still in the buffer when it is cleared, reused
+/// for another reception or dropped get closed.
+pub struct CmsgBuf<'slice> {
+    buf: MsgBuf<'slice>,
+    /// Offset of the aligned start of the control message area from the base of `buf`.
+    off: usize,
+    truncated: bool,
+}
+impl<'slice> CmsgBuf<'slice> {
+    /// Wraps the given buffer.
+    ///
+    /// The contents of the buffer are discarded, though its quota is retained.
+    #[inline]
+    pub fn new(mut buf: MsgBuf<'slice>) -> Self {
+        buf.set_fill(0);
+        buf.has_msg = false;
+        Self { buf, off: 0, truncated: false }
+    }
+    /// Returns the wrapped buffer.
+    #[inline]
+    pub fn into_inner(mut self) -> MsgBuf<'slice> {
+        self.close_fds();
+        take(&mut self.buf)
+    }
+
+    /// Returns the number of bytes of control messages that can be received without growing the
+    /// buffer.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        let off = self.buf.as_ptr().align_offset(align_of::<cmsghdr>());
+        self.buf.capacity().saturating_sub(off)
+    }
+    /// Returns the quota of the wrapped buffer.
+    #[inline]
+    pub fn quota(&self) -> Option<usize> {
+        self.buf.quota
+    }
+    /// Sets the quota of the wrapped buffer.
+    #[inline]
+    pub fn set_quota(&mut self, quota: Option<usize>) {
+        self.buf.quota = quota;
+    }
+    /// Returns `true` if the control messages received last did not fit into the buffer.
+    #[inline]
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+    /// Returns the length of the received control messages, in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buf.len_filled() - self.off
+    }
+    /// Returns `true` if no control messages have been received.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Discards the received control messages, closing the file descriptors that have not been
+    /// taken out of them.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.close_fds();
+        self.buf.set_fill(self.off);
+        self.buf.has_msg = false;
+        self.truncated = false;
+    }
+    /// Takes ownership of the file descriptors received via `SCM_RIGHTS`, appending them to `fds` in
+    /// the order in which they were received. Returns how many were taken.
+    ///
+    /// The `SCM_RIGHTS` messages remain in the buffer, but the descriptors in them are replaced with
+    /// `-1`, so that they will not be closed by the buffer, nor taken out again.
+    pub fn take_fds(&mut self, fds: &mut Vec<OwnedFd>) -> usize {
+        let before = fds.len();
+        self.map_fds(|fd| {
+            // SAFETY: the system has just installed this descriptor for us, and it can only be
+            // taken once since it's replaced with -1
+            fds.push(unsafe { OwnedFd::from_raw_fd(fd) });
+            -1
+        });
+        fds.len() - before
+    }
+    fn close_fds(&mut self) {
+        self.map_fds(|fd| {
+            // SAFETY: as above
+            drop(unsafe { OwnedFd::from_raw_fd(fd) });
+            -1
+        });
+    }
+    /// Calls `f` on every valid file descriptor in every `SCM_RIGHTS` message, replacing it with
+    /// the return value.
+    fn map_fds(&mut self, mut f: impl FnMut(c_int) -> c_int) {
+        if !self.buf.has_msg {
+            return;
+        }
+        let off = self.off;
+        let area = &mut self.buf.filled_part_mut()[off..];
+        let mut pos = 0;
+        while pos < area.len() {
+            let mut cmsgs = Cmsgs { rem: &area[pos..] };
+            let (rights, start, len) = match cmsgs.next() {
+                Some(c) if !c.data.is_empty() => (
+                    (c.level, c.ty) == (SOL_SOCKET, SCM_RIGHTS),
+                    c.data.as_ptr() as usize - area.as_ptr() as usize,
+                    c.data.len(),
+                ),
+                Some(_) => (false, 0, 0),
+                None => break,
+            };
+            pos = area.len() - cmsgs.rem.len();
+            if !rights {
+                continue;
+            }
+            for slot in area[start..start + len].chunks_exact_mut(size_of::<c_int>()) {
+                let slot = slot.as_mut_ptr().cast::<c_int>();
+                // SAFETY: the slot is in bounds; it is not necessarily aligned
+                let fd = unsafe { ptr::read_unaligned(slot) };
+                if fd >= 0 {
+                    unsafe { ptr::write_unaligned(slot, f(fd)) };
+                }
+            }
+        }
+    }
+
+    /// Discards the received control messages and grows the buffer by an unspecified amount.
+    #[inline]
+    pub fn clear_and_grow(&mut self) -> Result<(), QuotaExceeded> {
+        self.clear_and_grow_to(self.buf.capacity() + 1)
+    }
+    /// Discards the received control messages and ensures that the wrapped buffer has at least the
+    /// given capacity.
+    #[inline]
+    pub fn clear_and_grow_to(&mut self, new_cap: usize) -> Result<(), QuotaExceeded> {
+        self.clear();
+        self.buf.clear_and_grow_to(new_cap)?;
+        self.off = 0;
+        Ok(())
+    }
+    /// Same as [`.clear_and_grow()`](Self::clear_and_grow), but reports memory allocation failure
+    /// instead of aborting.
+    #[inline]
+    pub fn try_clear_and_grow(&mut self) -> Result<(), GrowError> {
+        self.try_clear_and_grow_to(self.buf.capacity() + 1)
+    }
+    /// Same as [`.clear_and_grow_to()`](Self::clear_and_grow_to), but reports memory allocation
+    /// failure instead of aborting.
+    #[inline]
+    pub fn try_clear_and_grow_to(&mut self, new_cap: usize) -> Result<(), GrowError> {
+        self.clear();
+        self.buf.try_clear_and_grow_to(new_cap)?;
+        self.off = 0;
+        Ok(())
+    }
+    pub(crate) fn try_clear_and_grow_to_quota(&mut self) -> Result<(), GrowError> {
+        self.clear();
+        self.buf.try_clear_and_grow_to_quota()?;
+        self.off = 0;
+        Ok(())
+    }
+
+    /// Iterates over the received control messages.
+    #[inline]
+    pub fn iter(&self) -> Cmsgs<'_> {
+        Cmsgs { rem: self.buf.msg().map(|m| &m[self.off..]).unwrap_or_default() }
+    }
+
+    /// Prepares the buffer for a `recvmsg()` call, returning the values for `msg_control` and
+    /// `msg_controllen`.
+    pub(crate) fn prepare(&mut self) -> (*mut c_void, usize) {
+        self.clear();
+        let off = self.buf.as_ptr().align_offset(align_of::<cmsghdr>());
+        let off = min(off, self.buf.capacity());
+        if self.buf.len_init() < off {
+            // The padding must be initialized for the fill cursor to be placed after it.
+            self.buf[..off].fill(MaybeUninit::new(0));
+            unsafe { self.buf.set_init(off) };
+        }
+        self.buf.set_fill(off);
+        self.off = off;
+        let area = &mut self.buf[off..];
+        (area.as_mut_ptr().cast(), area.len())
+    }
+    /// Records the results of a `recvmsg()` call made with the values returned by
+    /// [`.prepare()`](Self::prepare).
+    ///
+    /// # Safety
+    /// `len` bytes after the start of the area returned by `.prepare()` must have been initialized.
+    pub(crate) unsafe fn set_received(&mut self, len: usize, truncated: bool) {
+        let end = self.off + min(len, self.capacity());
+        unsafe { self.buf.advance_init_and_set_fill(end) };
+        self.buf.has_msg = true;
+        self.truncated = truncated;
+        #[cfg(not(any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "freebsd",
+            target_os = "dragonfly",
+            target_os = "netbsd",
+            target_os = "openbsd",
+        )))]
+        self.map_fds(|fd| {
+            // Best effort in the absence of MSG_CMSG_CLOEXEC.
+            unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) };
+            fd
+        });
+    }
+}
+impl Drop for CmsgBuf<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.close_fds();
+    }
+}
+impl Default for CmsgBuf<'_> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(MsgBuf::default())
+    }
+}
+impl<'slice> From<MsgBuf<'slice>> for CmsgBuf<'slice> {
+    #[inline]
+    fn from(buf: MsgBuf<'slice>) -> Self {
+        Self::new(buf)
+    }
+}
+impl Debug for CmsgBuf<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CmsgBuf")
+            .field("buf", &self.buf)
+            .field("off", &self.off)
+            .field("truncated", &self.truncated)
+            .finish()
+    }
+}
+impl<'a> IntoIterator for &'a CmsgBuf<'_> {
+    type Item = Cmsg<'a>;
+    type IntoIter = Cmsgs<'a>;
+    #[inline]
+    fn into_iter(self) -> Cmsgs<'a> {
+        self.iter()
+    }
+}
+
+/// Iterator over the control messages in a [`CmsgBuf`].
+#[derive(Clone, Debug)]
+pub struct Cmsgs<'a> {
+    rem: &'a [u8],
+}
+impl<'a> Iterator for Cmsgs<'a> {
+    type Item = Cmsg<'a>;
+    fn next(&mut self) -> Option<Cmsg<'a>> {
+        if self.rem.len() < size_of::<cmsghdr>() {
+            return None;
+        }
+        let hdr = unsafe {
+            // SAFETY: the buffer is aligned for cmsghdr and long enough, and every control message
+            // is padded to the alignment of cmsghdr
+            ptr::read(self.rem.as_ptr().cast::<cmsghdr>())
+        };
+        let hdr_len = unsafe { CMSG_LEN(0) } as usize;
+        let len = min(hdr.cmsg_len as usize, self.rem.len());
+        let data = self.rem.get(hdr_len..len).unwrap_or_default();
+        let space = unsafe { CMSG_SPACE((len - min(hdr_len, len)) as _) } as usize;
+        self.rem = self.rem.get(space..).unwrap_or_default();
+        if len < hdr_len {
+            // Malformed (or truncated to nothing); nothing after it can be trusted.
+            self.rem = &[];
+        }
+        Some(Cmsg { level: hdr.cmsg_level, ty: hdr.cmsg_type, data })
+    }
+}
+
+/// A single control message.
+#[derive(Copy, Clone, Debug)]
+pub struct Cmsg<'a> {
+    /// The protocol level of the message (`cmsg_level`), such as `SOL_SOCKET` or `IPPROTO_IP`.
+    pub level: c_int,
+    /// The protocol-specific type of the message (`cmsg_type`), such as `SCM_RIGHTS`.
+    pub ty: c_int,
+    /// The payload of the message.
+    pub data: &'a [u8],
+}
+impl Cmsg<'_> {
+    /// Reads the payload as a value of type `T`, returning `None` if it's too short.
+    ///
+    /// # Safety
+    /// `T` must be valid for any bit pattern, such as a `#[repr(C)]` struct of integers.
+    #[inline]
+    pub unsafe fn read_data<T: Copy>(&self) -> Option<T> {
+        (self.data.len() >= size_of::<T>())
+            .then(|| unsafe { ptr::read_unaligned(self.data.as_ptr().cast::<T>()) })
+    }
+    #[allow(dead_code)]
+    fn read_int(&self) -> Option<c_int> {
+        unsafe { self.read_data::<c_int>() }
+    }
+
+    /// Returns the number of file descriptors in this message if it is an `SCM_RIGHTS` message.
+    ///
+    /// Descriptors that have already been taken out with [`CmsgBuf::take_fds()`] are counted too.
+    pub fn fd_count(&self) -> Option<usize> {
+        ((self.level, self.ty) == (SOL_SOCKET, SCM_RIGHTS))
+            .then(|| self.data.len() / size_of::<c_int>())
+    }
+
+    /// Returns the TTL or hop limit of the packet if this is an `IP_TTL` or `IPV6_HOPLIMIT` message
+    /// (enabled via the `IP_RECVTTL` and `IPV6_RECVHOPLIMIT` socket options).
+    ///
+    /// Linux-only.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn ttl(&self) -> Option<u8> {
+        use libc::{IPPROTO_IP, IPPROTO_IPV6, IPV6_HOPLIMIT, IP_TTL};
+        match (self.level, self.ty) {
+            (IPPROTO_IP, IP_TTL) | (IPPROTO_IPV6, IPV6_HOPLIMIT) => {
+                self.read_int().map(|i| i as u8)
+            }
+            _ => None,
+        }
+    }
+    /// Returns the type of service or traffic class of the packet if this is an `IP_TOS` or
+    /// `IPV6_TCLASS` message (enabled via the `IP_RECVTOS` and `IPV6_RECVTCLASS` socket options).
+    ///
+    /// Linux-only.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn tos(&self) -> Option<u8> {
+        use libc::{IPPROTO_IP, IPPROTO_IPV6, IPV6_TCLASS, IP_TOS};
+        match (self.level, self.ty) {
+            (IPPROTO_IP, IP_TOS) => self.data.first().copied(),
+            (IPPROTO_IPV6, IPV6_TCLASS) => self.read_int().map(|i| i as u8),
+            _ => None,
+        }
+    }
+}
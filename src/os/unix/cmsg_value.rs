@@ -0,0 +1,114 @@
+use super::{AsyncRecvMsgWithCmsg, Cmsg, CmsgBuf, RecvMsgWithCmsg};
+use crate::{MsgBuf, RecvResult};
+use core::{
+    fmt::{self, Debug, Formatter},
+    future::Future,
+    mem::{align_of, size_of, MaybeUninit},
+    pin::Pin,
+    task::{Context, Poll},
+};
+use libc::cmsghdr;
+
+type Extract<T> = fn(&Cmsg<'_>) -> Option<T>;
+
+/// Returns the size of a control buffer that fits one control message with a payload of `len`
+/// bytes regardless of how the start of the buffer happens to be aligned.
+pub(super) const fn cbuf_size(len: usize) -> usize {
+    let align = align_of::<cmsghdr>();
+    let space = size_of::<cmsghdr>() + (len + align - 1) / align * align;
+    space + align - 1
+}
+
+/// Receives one message using the given buffer, with control messages going into a stack buffer
+/// of `N` bytes, and stores the first value `extract` finds among them in `out`.
+///
+/// The control buffer only moves to the heap if more control messages arrive than it fits, which
+/// does not count as the message spilling. Any file descriptors received along the way are closed.
+pub(super) fn recv_with_cmsg_value<RM: RecvMsgWithCmsg + ?Sized, T, const N: usize>(
+    recver: &mut RM,
+    buf: &mut MsgBuf<'_>,
+    out: &mut Option<T>,
+    abuf: Option<&mut RM::AddrBuf>,
+    extract: Extract<T>,
+) -> Result<RecvResult, RM::Error> {
+    let mut cbufa = [MaybeUninit::uninit(); N];
+    let mut cbuf = CmsgBuf::new(MsgBuf::from(cbufa.as_mut()));
+    let rslt = recver.recv_msg_with_cmsg(buf, &mut cbuf, abuf)?;
+    *out = cbuf.iter().find_map(|c| extract(&c));
+    Ok(rslt)
+}
+
+/// Future type returned by the methods of
+/// [`AsyncRecvMsgWithCredentialsExt`](super::AsyncRecvMsgWithCredentialsExt),
+/// [`AsyncRecvMsgWithTimestampExt`](super::AsyncRecvMsgWithTimestampExt) and
+/// [`AsyncRecvMsgWithPacketInfoExt`](super::AsyncRecvMsgWithPacketInfoExt), which receive a message
+/// together with a value of type `T` found among its control messages.
+///
+/// The control buffer of `N` bytes is stored inline, so that awaiting the future allocates nothing
+/// unless more control messages arrive than it fits.
+pub struct RecvWithCmsgValue<
+    'io,
+    'buf,
+    'slice,
+    'out,
+    'abuf,
+    RM: AsyncRecvMsgWithCmsg + ?Sized,
+    T,
+    const N: usize,
+> {
+    recver: &'io mut RM,
+    buf: &'buf mut MsgBuf<'slice>,
+    out: &'out mut Option<T>,
+    abuf: Option<&'abuf mut RM::AddrBuf>,
+    cbufa: [MaybeUninit<u8>; N],
+    extract: Extract<T>,
+}
+impl<'io, 'buf, 'slice, 'out, 'abuf, RM: AsyncRecvMsgWithCmsg + ?Sized, T, const N: usize>
+    RecvWithCmsgValue<'io, 'buf, 'slice, 'out, 'abuf, RM, T, N>
+{
+    pub(super) fn new(
+        recver: &'io mut RM,
+        buf: &'buf mut MsgBuf<'slice>,
+        out: &'out mut Option<T>,
+        abuf: Option<&'abuf mut RM::AddrBuf>,
+        extract: Extract<T>,
+    ) -> Self {
+        Self { recver, buf, out, abuf, cbufa: [MaybeUninit::uninit(); N], extract }
+    }
+}
+impl<RM: AsyncRecvMsgWithCmsg + Unpin + ?Sized, T, const N: usize> Future
+    for RecvWithCmsgValue<'_, '_, '_, '_, '_, RM, T, N>
+{
+    type Output = Result<RecvResult, RM::Error>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let RecvWithCmsgValue { recver, buf, out, abuf, cbufa, extract } = self.get_mut();
+        // Nothing is left in the control buffer by a poll that returns Pending, so it can just as
+        // well be set up anew every time.
+        let mut cbuf = CmsgBuf::new(MsgBuf::from(cbufa.as_mut()));
+        let rslt = match Pin::new(&mut **recver).poll_recv_msg_with_cmsg(
+            cx,
+            buf,
+            &mut cbuf,
+            abuf.as_deref_mut(),
+        ) {
+            Poll::Ready(r) => r?,
+            Poll::Pending => return Poll::Pending,
+        };
+        **out = cbuf.iter().find_map(|c| extract(&c));
+        Poll::Ready(Ok(rslt))
+    }
+}
+impl<RM: AsyncRecvMsgWithCmsg + Debug + ?Sized, T: Debug, const N: usize> Debug
+    for RecvWithCmsgValue<'_, '_, '_, '_, '_, RM, T, N>
+where
+    RM::AddrBuf: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecvWithCmsgValue")
+            .field("recver", &self.recver)
+            .field("buf", &self.buf)
+            .field("out", &self.out)
+            .field("abuf", &self.abuf)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,101 @@
+use super::{
+    cmsg_value::{cbuf_size, recv_with_cmsg_value},
+    setsockopt_int, AsyncRecvMsgWithCmsg, Cmsg, RecvMsgWithCmsg, RecvWithCmsgValue,
+};
+use crate::{MsgBuf, RecvResult};
+use core::mem::size_of;
+use libc::{c_int, gid_t, pid_t, ucred, uid_t, SCM_CREDENTIALS, SOL_SOCKET, SO_PASSCRED};
+use std::{io, os::fd::AsFd};
+
+const CRED_CBUF_SIZE: usize = cbuf_size(size_of::<ucred>());
+
+/// Credentials of the process that sent a message over a Unix domain socket, as received via
+/// `SCM_CREDENTIALS`.
+///
+/// Linux-only.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Credentials {
+    /// The process ID of the sender.
+    pub pid: pid_t,
+    /// The user ID of the sender.
+    pub uid: uid_t,
+    /// The group ID of the sender.
+    pub gid: gid_t,
+}
+impl From<ucred> for Credentials {
+    #[inline]
+    fn from(c: ucred) -> Self {
+        Self { pid: c.pid, uid: c.uid, gid: c.gid }
+    }
+}
+
+impl Cmsg<'_> {
+    /// Returns the credentials of the sender if this is an `SCM_CREDENTIALS` message.
+    ///
+    /// Linux-only.
+    pub fn credentials(&self) -> Option<Credentials> {
+        if (self.level, self.ty) != (SOL_SOCKET, SCM_CREDENTIALS) {
+            return None;
+        }
+        // SAFETY: ucred is a struct of integers
+        unsafe { self.read_data::<ucred>() }.map(Credentials::from)
+    }
+}
+
+/// Enables or disables the `SO_PASSCRED` socket option, which makes the system attach the
+/// credentials of the sender to every message received from then on.
+///
+/// Linux-only.
+pub fn set_pass_credentials(socket: impl AsFd, enable: bool) -> io::Result<()> {
+    setsockopt_int(socket.as_fd(), SOL_SOCKET, SO_PASSCRED, enable as c_int)
+}
+
+/// Receiving the credentials of the sender together with messages.
+///
+/// Requires `SO_PASSCRED` to be enabled on the socket beforehand, which can be done via
+/// [`set_pass_credentials()`].
+pub trait RecvMsgWithCredentials: RecvMsgWithCmsg {
+    /// Receives one message using the given buffer, storing the credentials of its sender in
+    /// `creds`, or `None` if there were none attached to the message.
+    ///
+    /// The control messages are received into a small buffer on the stack, which only grows if
+    /// control messages other than credentials arrive. Any file descriptors received alongside the
+    /// credentials are closed.
+    fn recv_msg_with_credentials(
+        &mut self,
+        buf: &mut MsgBuf<'_>,
+        creds: &mut Option<Credentials>,
+        abuf: Option<&mut Self::AddrBuf>,
+    ) -> Result<RecvResult, Self::Error> {
+        recv_with_cmsg_value::<_, _, CRED_CBUF_SIZE>(self, buf, creds, abuf, |c| c.credentials())
+    }
+}
+impl<T: RecvMsgWithCmsg + ?Sized> RecvMsgWithCredentials for T {}
+
+/// Futures for receiving the credentials of the sender together with messages.
+///
+/// This is the async version of [`RecvMsgWithCredentials`].
+pub trait AsyncRecvMsgWithCredentialsExt: AsyncRecvMsgWithCmsg {
+    /// Receives one message using the given buffer, storing the credentials of its sender in
+    /// `creds`.
+    ///
+    /// See [`RecvMsgWithCredentials::recv_msg_with_credentials()`] for details.
+    #[inline]
+    fn recv_msg_with_credentials<'io, 'buf, 'slice: 'buf, 'creds, 'abuf>(
+        &'io mut self,
+        buf: &'buf mut MsgBuf<'slice>,
+        creds: &'creds mut Option<Credentials>,
+        abuf: Option<&'abuf mut Self::AddrBuf>,
+    ) -> RecvWithCredentials<'io, 'buf, 'slice, 'creds, 'abuf, Self>
+    where
+        Self: Unpin,
+    {
+        RecvWithCmsgValue::new(self, buf, creds, abuf, |c| c.credentials())
+    }
+}
+impl<T: AsyncRecvMsgWithCmsg + ?Sized> AsyncRecvMsgWithCredentialsExt for T {}
+
+/// Future type returned by
+/// [`.recv_msg_with_credentials()`](AsyncRecvMsgWithCredentialsExt::recv_msg_with_credentials).
+pub type RecvWithCredentials<'io, 'buf, 'slice, 'creds, 'abuf, RM> =
+    RecvWithCmsgValue<'io, 'buf, 'slice, 'creds, 'abuf, RM, Credentials, CRED_CBUF_SIZE>;
@@ -0,0 +1,212 @@
+use super::mmap::{page_size, round_to_pages};
+use crate::msgbuf::{AllocFailed, OwnedBuf, OwnedBufRawParts};
+use core::{
+    num::NonZeroUsize,
+    ptr::{self, NonNull},
+};
+use libc::{
+    c_void, MAP_ANONYMOUS, MAP_FAILED, MAP_FIXED, MAP_PRIVATE, MAP_SHARED, MFD_CLOEXEC, PROT_READ,
+    PROT_WRITE,
+};
+use std::{
+    io,
+    os::fd::{AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd},
+};
+
+/// An [owned buffer](OwnedBuf) backed by a shared memory region created with `memfd_create()`,
+/// whose file descriptor can be handed to another process (via `SCM_RIGHTS`, for instance) so that
+/// a message received into it can be read there without copying.
+///
+/// The message starts at offset zero of the file. Growth extends the file with `ftruncate()` and
+/// maps it anew, which doesn't copy the contents either. The capacity is always a multiple of the
+/// page size, and since the extended parts of the file read as zeroes, the whole buffer is
+/// considered initialized.
+///
+/// The process that the descriptor is handed to must not shrink the file while the buffer is in
+/// use, as that makes accesses past the new end of the file fault.
+///
+/// Linux-only.
+#[derive(Debug)]
+pub struct MemfdBuf {
+    /// Points one page past the start of the mapping, the first page of which is private and holds
+    /// the file descriptor, so that the buffer can be recovered from its raw parts.
+    ptr: NonNull<u8>,
+    cap: usize,
+    init: usize,
+}
+// SAFETY: owns the mapping and the file descriptor
+unsafe impl Send for MemfdBuf {}
+unsafe impl Sync for MemfdBuf {}
+
+impl MemfdBuf {
+    /// Creates an empty buffer, which does not create the memfd until it grows.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { ptr: NonNull::dangling(), cap: 0, init: 0 }
+    }
+    /// Creates a buffer with at least the given capacity.
+    pub fn with_capacity(cap: usize) -> io::Result<Self> {
+        let mut slf = Self::new();
+        if cap > 0 {
+            slf.create(round_to_pages(cap).ok_or(io::ErrorKind::OutOfMemory)?)?;
+        }
+        Ok(slf)
+    }
+
+    /// Returns the file descriptor of the memfd, or `None` if the buffer is empty and thus has none
+    /// yet.
+    #[inline]
+    pub fn fd(&self) -> Option<BorrowedFd<'_>> {
+        if self.cap == 0 {
+            return None;
+        }
+        // SAFETY: the descriptor stays open for as long as `self` does
+        Some(unsafe { BorrowedFd::borrow_raw(self.raw_fd()) })
+    }
+    /// Unmaps the buffer and returns the file descriptor of the memfd, or `None` if the buffer is
+    /// empty and thus has none.
+    pub fn into_fd(self) -> Option<OwnedFd> {
+        if self.cap == 0 {
+            return None;
+        }
+        let fd = unsafe { OwnedFd::from_raw_fd(self.raw_fd()) };
+        unsafe { self.unmap() };
+        core::mem::forget(self);
+        Some(fd)
+    }
+
+    fn raw_fd(&self) -> RawFd {
+        unsafe { self.header().read() }
+    }
+    fn header(&self) -> *mut RawFd {
+        unsafe { self.ptr.as_ptr().sub(page_size()).cast() }
+    }
+    /// Unmaps both the header page and the file.
+    unsafe fn unmap(&self) {
+        unsafe { libc::munmap(self.header().cast(), page_size() + self.cap) };
+    }
+
+    /// Creates the memfd with the given length, which must be a non-zero multiple of the page size.
+    fn create(&mut self, len: usize) -> io::Result<()> {
+        let fd = unsafe { libc::memfd_create(b"recvmsg\0".as_ptr().cast(), MFD_CLOEXEC) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+        truncate(fd.as_raw_fd(), len)?;
+        self.ptr = map(fd.as_raw_fd(), len)?;
+        (self.cap, self.init) = (len, len);
+        core::mem::forget(fd); // Now owned by the header
+        Ok(())
+    }
+    /// Extends the memfd to the given length, which must be a multiple of the page size larger than
+    /// the current capacity, and maps it anew.
+    fn extend(&mut self, len: usize) -> io::Result<()> {
+        let fd = self.raw_fd();
+        truncate(fd, len)?;
+        match map(fd, len) {
+            Ok(ptr) => {
+                unsafe { self.unmap() };
+                self.ptr = ptr;
+                (self.cap, self.init) = (len, len);
+                Ok(())
+            }
+            Err(e) => {
+                let _ = truncate(fd, self.cap);
+                Err(e)
+            }
+        }
+    }
+}
+
+fn truncate(fd: RawFd, len: usize) -> io::Result<()> {
+    if unsafe { libc::ftruncate(fd, len as _) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+/// Maps a private header page holding `fd` followed by `len` bytes of the file, returning a pointer
+/// to the latter.
+fn map(fd: RawFd, len: usize) -> io::Result<NonNull<u8>> {
+    let page = page_size();
+    let prot = PROT_READ | PROT_WRITE;
+    let base = unsafe {
+        libc::mmap(ptr::null_mut(), page + len, prot, MAP_PRIVATE | MAP_ANONYMOUS, -1, 0)
+    };
+    if base == MAP_FAILED {
+        return Err(io::Error::last_os_error());
+    }
+    let data = unsafe { base.cast::<u8>().add(page) }.cast::<c_void>();
+    let file = unsafe { libc::mmap(data, len, prot, MAP_SHARED | MAP_FIXED, fd, 0) };
+    if file == MAP_FAILED {
+        let e = io::Error::last_os_error();
+        unsafe { libc::munmap(base, page + len) };
+        return Err(e);
+    }
+    unsafe { base.cast::<RawFd>().write(fd) };
+    Ok(NonNull::new(data.cast()).unwrap())
+}
+
+impl Default for MemfdBuf {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Drop for MemfdBuf {
+    fn drop(&mut self) {
+        if self.cap > 0 {
+            let fd = self.raw_fd();
+            unsafe {
+                self.unmap();
+                libc::close(fd);
+            }
+        }
+    }
+}
+
+unsafe impl OwnedBuf for MemfdBuf {
+    // No Linux platform has pages smaller than this
+    const ALIGNMENT: usize = 4096;
+    #[inline]
+    unsafe fn from_raw_parts(OwnedBufRawParts { ptr, cap, init }: OwnedBufRawParts) -> Self {
+        Self { ptr, cap, init }
+    }
+    #[inline]
+    fn as_raw_parts(&self) -> OwnedBufRawParts {
+        OwnedBufRawParts { ptr: self.ptr, cap: self.cap, init: self.init }
+    }
+    /// # Panics
+    /// If creating, extending or mapping the memfd fails.
+    fn grow(&mut self, new_cap: usize) {
+        if let Err(e) = self.try_grow(new_cap) {
+            panic!("{e}");
+        }
+    }
+    fn try_grow(&mut self, new_cap: usize) -> Result<(), AllocFailed> {
+        if new_cap <= self.cap {
+            return Ok(());
+        }
+        let attempted_alloc = NonZeroUsize::new(new_cap).unwrap();
+        let new_len = round_to_pages(new_cap).ok_or(AllocFailed { attempted_alloc })?;
+        let rslt = if self.cap == 0 { self.create(new_len) } else { self.extend(new_len) };
+        rslt.map_err(|_| AllocFailed { attempted_alloc })
+    }
+    /// Unmaps the pages past the new capacity and truncates the memfd accordingly.
+    fn shrink(&mut self, new_cap: usize) {
+        let new_len = match round_to_pages(new_cap) {
+            Some(l) if l < self.cap => l,
+            _ => return,
+        };
+        if new_len == 0 {
+            *self = Self::new();
+            return;
+        }
+        let tail = unsafe { self.ptr.as_ptr().add(new_len) };
+        if unsafe { libc::munmap(tail.cast(), self.cap - new_len) } == 0 {
+            let _ = truncate(self.raw_fd(), new_len);
+            self.cap = new_len;
+            self.init = self.init.min(new_len);
+        }
+    }
+}
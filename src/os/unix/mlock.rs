@@ -0,0 +1,107 @@
+use crate::msgbuf::{move_contents, owned_default, AllocFailed, OwnedBuf, OwnedBufRawParts};
+use alloc::vec::Vec;
+use core::{
+    fmt::{self, Debug, Formatter},
+    num::NonZeroUsize,
+};
+
+/// An [owned buffer](OwnedBuf) wrapper that locks the memory of the inner buffer with `mlock()`,
+/// keeping it from being swapped out to disk.
+///
+/// Growth and shrinking allocate a new buffer, lock it, copy the contents over and unlock the old
+/// one, which is then dropped without being wiped – wrap this in
+/// [`ZeroizingBuf`](crate::msgbuf::ZeroizingBuf) to have that taken care of as well. If locking
+/// fails (due to `RLIMIT_MEMLOCK`, for instance), `.try_grow()` fails and `.grow()` panics.
+///
+/// Memory locks apply to whole pages and don't nest. With an inner buffer that shares pages with
+/// other allocations, such as `Vec`, this means that unlocking the buffer also unlocks whatever
+/// else lives on its first and last page, and that other code unlocking its own allocations can
+/// unlock parts of this buffer. [`MmapBuf`](super::MmapBuf), where available, has its own pages
+/// and is thus the better choice of inner buffer.
+pub struct Mlocked<Owned: OwnedBuf = Vec<u8>>(Owned);
+impl<Owned: OwnedBuf> Mlocked<Owned> {
+    /// Locks the memory of the given buffer and wraps it.
+    pub fn new(owned: Owned) -> Result<Self, AllocFailed> {
+        lock(&owned)?;
+        Ok(Self(owned))
+    }
+    /// Allocates and locks a buffer of the given capacity, then moves the contents to it.
+    fn realloc(&mut self, new_cap: usize) -> Result<(), AllocFailed> {
+        let mut new = owned_default::<Owned>();
+        new.try_grow(new_cap)?;
+        lock(&new)?;
+        let new = move_contents(&self.0, new);
+        unlock(&core::mem::replace(&mut self.0, new));
+        Ok(())
+    }
+}
+
+fn lock<Owned: OwnedBuf>(owned: &Owned) -> Result<(), AllocFailed> {
+    let raw = owned.as_raw_parts();
+    let attempted_alloc = match NonZeroUsize::new(raw.cap) {
+        Some(a) => a,
+        None => return Ok(()),
+    };
+    if unsafe { libc::mlock(raw.ptr.as_ptr().cast(), raw.cap) } < 0 {
+        return Err(AllocFailed { attempted_alloc });
+    }
+    Ok(())
+}
+fn unlock<Owned: OwnedBuf>(owned: &Owned) {
+    let raw = owned.as_raw_parts();
+    if raw.cap > 0 {
+        unsafe { libc::munlock(raw.ptr.as_ptr().cast(), raw.cap) };
+    }
+}
+
+impl<Owned: OwnedBuf + Default> Default for Mlocked<Owned> {
+    /// Wraps an empty buffer, which has nothing to lock.
+    #[inline]
+    fn default() -> Self {
+        Self(Owned::default())
+    }
+}
+impl<Owned: OwnedBuf> Debug for Mlocked<Owned> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("Mlocked(..)")
+    }
+}
+impl<Owned: OwnedBuf> Drop for Mlocked<Owned> {
+    fn drop(&mut self) {
+        unlock(&self.0);
+    }
+}
+
+unsafe impl<Owned: OwnedBuf> OwnedBuf for Mlocked<Owned> {
+    const ALIGNMENT: usize = Owned::ALIGNMENT;
+    const SENSITIVE: bool = Owned::SENSITIVE;
+    /// The memory of the buffer is assumed to be locked already.
+    #[inline]
+    unsafe fn from_raw_parts(raw_parts: OwnedBufRawParts) -> Self {
+        Self(unsafe { Owned::from_raw_parts(raw_parts) })
+    }
+    #[inline]
+    fn as_raw_parts(&self) -> OwnedBufRawParts {
+        self.0.as_raw_parts()
+    }
+    /// # Panics
+    /// If allocating or locking fails.
+    fn grow(&mut self, new_cap: usize) {
+        if let Err(e) = self.try_grow(new_cap) {
+            panic!("{e}");
+        }
+    }
+    fn try_grow(&mut self, new_cap: usize) -> Result<(), AllocFailed> {
+        if new_cap <= self.0.as_raw_parts().cap {
+            return Ok(());
+        }
+        self.realloc(new_cap)
+    }
+    /// Moves the contents to a smaller buffer, or keeps the current one if allocating or locking
+    /// that fails.
+    fn shrink(&mut self, new_cap: usize) {
+        if new_cap < self.0.as_raw_parts().cap {
+            let _ = self.realloc(new_cap);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use crate::msgbuf::{AllocFailed, OwnedBuf, OwnedBufRawParts};
+use core::{
+    num::NonZeroUsize,
+    ptr::{self, NonNull},
+};
+use libc::{
+    _SC_PAGESIZE, MAP_ANONYMOUS, MAP_FAILED, MAP_PRIVATE, MREMAP_MAYMOVE, PROT_READ, PROT_WRITE,
+};
+
+pub(super) fn page_size() -> usize {
+    unsafe { libc::sysconf(_SC_PAGESIZE) as usize }
+}
+/// Rounds up to a multiple of the page size, or returns `None` on overflow.
+pub(super) fn round_to_pages(len: usize) -> Option<usize> {
+    let mask = page_size() - 1;
+    Some(len.checked_add(mask)? & !mask)
+}
+
+/// An [owned buffer](OwnedBuf) backed by an anonymous memory mapping, which grows via
+/// `mremap(MREMAP_MAYMOVE)` and thus never copies its contents, however large they are.
+///
+/// The capacity is always a multiple of the page size. Since anonymous mappings are zero-filled,
+/// the whole buffer is considered initialized.
+///
+/// Best suited for messages of several megabytes, such as those that Unix domain sockets allow
+/// for; for small messages, the page granularity and system call overhead make `Vec` the better
+/// choice.
+///
+/// Linux-only.
+#[derive(Debug)]
+pub struct MmapBuf {
+    ptr: NonNull<u8>,
+    cap: usize,
+    init: usize,
+}
+// SAFETY: owns the mapping
+unsafe impl Send for MmapBuf {}
+unsafe impl Sync for MmapBuf {}
+
+impl MmapBuf {
+    /// Creates an empty buffer, which does not map anything until it grows.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { ptr: NonNull::dangling(), cap: 0, init: 0 }
+    }
+    /// Creates a buffer with at least the given capacity.
+    ///
+    /// # Panics
+    /// If the mapping fails.
+    pub fn with_capacity(cap: usize) -> Self {
+        let mut slf = Self::new();
+        slf.grow(cap);
+        slf
+    }
+
+    /// Maps or remaps to the given length, which must be a non-zero multiple of the page size.
+    fn remap(&mut self, new_len: usize) -> Result<(), AllocFailed> {
+        let ptr = unsafe {
+            if self.cap == 0 {
+                libc::mmap(
+                    ptr::null_mut(),
+                    new_len,
+                    PROT_READ | PROT_WRITE,
+                    MAP_PRIVATE | MAP_ANONYMOUS,
+                    -1,
+                    0,
+                )
+            } else {
+                libc::mremap(self.ptr.as_ptr().cast(), self.cap, new_len, MREMAP_MAYMOVE)
+            }
+        };
+        let attempted_alloc = NonZeroUsize::new(new_len).expect("zero-sized mapping");
+        if ptr == MAP_FAILED {
+            return Err(AllocFailed { attempted_alloc });
+        }
+        self.ptr = NonNull::new(ptr.cast()).ok_or(AllocFailed { attempted_alloc })?;
+        self.cap = new_len;
+        self.init = new_len;
+        Ok(())
+    }
+}
+impl Default for MmapBuf {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Drop for MmapBuf {
+    fn drop(&mut self) {
+        if self.cap > 0 {
+            unsafe { libc::munmap(self.ptr.as_ptr().cast(), self.cap) };
+        }
+    }
+}
+
+unsafe impl OwnedBuf for MmapBuf {
+    // No Linux platform has pages smaller than this
+    const ALIGNMENT: usize = 4096;
+    #[inline]
+    unsafe fn from_raw_parts(OwnedBufRawParts { ptr, cap, init }: OwnedBufRawParts) -> Self {
+        Self { ptr, cap, init }
+    }
+    #[inline]
+    fn as_raw_parts(&self) -> OwnedBufRawParts {
+        OwnedBufRawParts { ptr: self.ptr, cap: self.cap, init: self.init }
+    }
+    /// # Panics
+    /// If the mapping fails.
+    fn grow(&mut self, new_cap: usize) {
+        if let Err(e) = self.try_grow(new_cap) {
+            panic!("{e}");
+        }
+    }
+    fn try_grow(&mut self, new_cap: usize) -> Result<(), AllocFailed> {
+        if new_cap <= self.cap {
+            return Ok(());
+        }
+        let new_len = round_to_pages(new_cap)
+            .ok_or(AllocFailed { attempted_alloc: NonZeroUsize::new(new_cap).unwrap() })?;
+        self.remap(new_len)
+    }
+    /// Unmaps the pages past the new capacity.
+    fn shrink(&mut self, new_cap: usize) {
+        let new_len = match round_to_pages(new_cap) {
+            Some(l) if l < self.cap => l,
+            _ => return,
+        };
+        if new_len == 0 {
+            *self = Self::new();
+            return;
+        }
+        let tail = unsafe { self.ptr.as_ptr().add(new_len) };
+        if unsafe { libc::munmap(tail.cast(), self.cap - new_len) } == 0 {
+            self.cap = new_len;
+            self.init = self.init.min(new_len);
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use super::{
+    cmsg_value::{cbuf_size, recv_with_cmsg_value},
+    getsockopt_int, setsockopt_int, AsyncRecvMsgWithCmsg, Cmsg, RecvMsgWithCmsg, RecvWithCmsgValue,
+};
+use crate::{MsgBuf, RecvResult};
+use core::mem::size_of;
+use libc::{
+    c_int, in6_pktinfo, in_pktinfo, AF_INET6, IPPROTO_IP, IPPROTO_IPV6, IPV6_PKTINFO,
+    IPV6_RECVPKTINFO, IP_PKTINFO, SOL_SOCKET, SO_DOMAIN,
+};
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    os::fd::AsFd,
+};
+
+const PI_CBUF_SIZE: usize = cbuf_size(size_of::<in6_pktinfo>());
+
+/// Information about where a packet was received, as received via `IP_PKTINFO` or
+/// `IPV6_PKTINFO`.
+///
+/// Linux-only.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PacketInfo {
+    /// The destination address from the header of the packet, i.e. the local address it was sent
+    /// to.
+    pub dst: IpAddr,
+    /// The index of the interface on which the packet was received.
+    pub ifindex: u32,
+}
+
+impl Cmsg<'_> {
+    /// Returns the packet information if this is an `IP_PKTINFO` or `IPV6_PKTINFO` message.
+    ///
+    /// Linux-only.
+    pub fn packet_info(&self) -> Option<PacketInfo> {
+        // SAFETY: in_pktinfo and in6_pktinfo are structs of integers
+        unsafe {
+            match (self.level, self.ty) {
+                (IPPROTO_IP, IP_PKTINFO) => self.read_data::<in_pktinfo>().map(|pi| PacketInfo {
+                    dst: Ipv4Addr::from(u32::from_be(pi.ipi_addr.s_addr)).into(),
+                    ifindex: pi.ipi_ifindex as u32,
+                }),
+                (IPPROTO_IPV6, IPV6_PKTINFO) => {
+                    self.read_data::<in6_pktinfo>().map(|pi| PacketInfo {
+                        dst: Ipv6Addr::from(pi.ipi6_addr.s6_addr).into(),
+                        ifindex: pi.ipi6_ifindex,
+                    })
+                }
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Enables or disables reception of [packet information](PacketInfo) on a UDP socket.
+///
+/// For IPv4 sockets, this sets `IP_PKTINFO`. For IPv6 sockets, `IPV6_RECVPKTINFO` is set, and so is
+/// `IP_PKTINFO` where supported, so that IPv4 packets received by dual-stack sockets carry packet
+/// information as well.
+///
+/// Linux-only.
+pub fn set_recv_packet_info(socket: impl AsFd, enable: bool) -> io::Result<()> {
+    let fd = socket.as_fd();
+    let enable = enable as c_int;
+    if getsockopt_int(fd, SOL_SOCKET, SO_DOMAIN)? == AF_INET6 {
+        setsockopt_int(fd, IPPROTO_IPV6, IPV6_RECVPKTINFO, enable)?;
+        // Not supported by IPv6-only sockets on older kernels, which receive no IPv4 packets anyway.
+        let _ = setsockopt_int(fd, IPPROTO_IP, IP_PKTINFO, enable);
+        Ok(())
+    } else {
+        setsockopt_int(fd, IPPROTO_IP, IP_PKTINFO, enable)
+    }
+}
+
+/// Receiving the destination address and interface of packets together with them.
+///
+/// Requires packet information reception to be enabled on the socket beforehand, which can be
+/// done via [`set_recv_packet_info()`].
+pub trait RecvMsgWithPacketInfo: RecvMsgWithCmsg {
+    /// Receives one message using the given buffer, storing the information about where it was
+    /// received in `pi`, or `None` if there was none attached to the message.
+    ///
+    /// The control messages are received into a small buffer on the stack, which only grows if
+    /// control messages other than packet information arrive.
+    fn recv_msg_with_packet_info(
+        &mut self,
+        buf: &mut MsgBuf<'_>,
+        pi: &mut Option<PacketInfo>,
+        abuf: Option<&mut Self::AddrBuf>,
+    ) -> Result<RecvResult, Self::Error> {
+        recv_with_cmsg_value::<_, _, PI_CBUF_SIZE>(self, buf, pi, abuf, |c| c.packet_info())
+    }
+}
+impl<T: RecvMsgWithCmsg + ?Sized> RecvMsgWithPacketInfo for T {}
+
+/// Futures for receiving the destination address and interface of packets together with them.
+///
+/// This is the async version of [`RecvMsgWithPacketInfo`].
+pub trait AsyncRecvMsgWithPacketInfoExt: AsyncRecvMsgWithCmsg {
+    /// Receives one message using the given buffer, storing the information about where it was
+    /// received in `pi`.
+    ///
+    /// See [`RecvMsgWithPacketInfo::recv_msg_with_packet_info()`] for details.
+    #[inline]
+    fn recv_msg_with_packet_info<'io, 'buf, 'slice: 'buf, 'pi, 'abuf>(
+        &'io mut self,
+        buf: &'buf mut MsgBuf<'slice>,
+        pi: &'pi mut Option<PacketInfo>,
+        abuf: Option<&'abuf mut Self::AddrBuf>,
+    ) -> RecvWithPacketInfo<'io, 'buf, 'slice, 'pi, 'abuf, Self>
+    where
+        Self: Unpin,
+    {
+        RecvWithCmsgValue::new(self, buf, pi, abuf, |c| c.packet_info())
+    }
+}
+impl<T: AsyncRecvMsgWithCmsg + ?Sized> AsyncRecvMsgWithPacketInfoExt for T {}
+
+/// Future type returned by
+/// [`.recv_msg_with_packet_info()`](AsyncRecvMsgWithPacketInfoExt::recv_msg_with_packet_info).
+pub type RecvWithPacketInfo<'io, 'buf, 'slice, 'pi, 'abuf, RM> =
+    RecvWithCmsgValue<'io, 'buf, 'slice, 'pi, 'abuf, RM, PacketInfo, PI_CBUF_SIZE>;
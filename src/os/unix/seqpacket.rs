@@ -0,0 +1,243 @@
+//! Unix domain sockets of the `SOCK_SEQPACKET` type.
+
+#[cfg(feature = "tokio")]
+pub mod tokio;
+
+use crate::sync::r#impl::net::unix::wrap::extract_unix_address;
+use core::mem::{size_of, zeroed};
+use libc::{
+    c_int, sockaddr, sockaddr_storage, sockaddr_un, socklen_t, AF_UNIX, SOCK_SEQPACKET, SOMAXCONN,
+};
+use std::{
+    io,
+    net::Shutdown,
+    os::{
+        fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd},
+        unix::{ffi::OsStrExt, net::SocketAddr as UnixAddr},
+    },
+    path::Path,
+};
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+const SEND_FLAGS: c_int = libc::MSG_NOSIGNAL;
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+)))]
+const SEND_FLAGS: c_int = 0;
+
+fn cvt(ret: c_int) -> io::Result<c_int> {
+    if ret < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(ret)
+    }
+}
+
+/// Sets the close-on-exec flag on a newly created file descriptor, unless the system has already
+/// done that atomically.
+fn set_cloexec(fd: OwnedFd) -> io::Result<OwnedFd> {
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    cvt(unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_SETFD, libc::FD_CLOEXEC) })?;
+    Ok(fd)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const SOCK_FLAGS: c_int = libc::SOCK_CLOEXEC;
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+const SOCK_FLAGS: c_int = 0;
+
+fn socket() -> io::Result<OwnedFd> {
+    let fd = cvt(unsafe { libc::socket(AF_UNIX, SOCK_SEQPACKET | SOCK_FLAGS, 0) })?;
+    set_cloexec(unsafe { OwnedFd::from_raw_fd(fd) })
+}
+
+fn sockaddr_from_path(path: &Path) -> io::Result<(sockaddr_un, socklen_t)> {
+    let mut addr = unsafe { zeroed::<sockaddr_un>() };
+    addr.sun_family = AF_UNIX as _;
+    let bytes = path.as_os_str().as_bytes();
+    if bytes.contains(&0) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "path contains a nul byte"));
+    }
+    if bytes.len() >= addr.sun_path.len() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "path is too long"));
+    }
+    for (dst, &src) in addr.sun_path.iter_mut().zip(bytes) {
+        *dst = src as _;
+    }
+    let path_off = addr.sun_path.as_ptr() as usize - (&addr as *const sockaddr_un as usize);
+    Ok((addr, (path_off + bytes.len() + 1) as _))
+}
+
+fn sockname(
+    fd: BorrowedFd<'_>,
+    f: unsafe extern "C" fn(c_int, *mut sockaddr, *mut socklen_t) -> c_int,
+) -> io::Result<UnixAddr> {
+    let mut storage = unsafe { zeroed::<sockaddr_storage>() };
+    let mut len = size_of::<sockaddr_storage>() as socklen_t;
+    cvt(unsafe { f(fd.as_raw_fd(), (&mut storage as *mut sockaddr_storage).cast(), &mut len) })?;
+    extract_unix_address(&storage, len)
+}
+
+/// A Unix domain socket of the `SOCK_SEQPACKET` type, which is connection-oriented like a stream
+/// socket, but preserves message boundaries like a datagram socket.
+///
+/// Implements all of the reception traits that [`UnixDatagram`](std::os::unix::net::UnixDatagram)
+/// does, save for batch reception. End of stream is reported once the peer has shut down or
+/// closed its end of the connection and all of the messages sent before that have been received.
+#[derive(Debug)]
+pub struct UnixSeqpacket {
+    fd: OwnedFd,
+}
+impl UnixSeqpacket {
+    /// Connects to the socket bound to the given path.
+    pub fn connect(path: impl AsRef<Path>) -> io::Result<Self> {
+        let fd = socket()?;
+        let (addr, len) = sockaddr_from_path(path.as_ref())?;
+        cvt(unsafe { libc::connect(fd.as_raw_fd(), (&addr as *const sockaddr_un).cast(), len) })?;
+        Ok(Self { fd })
+    }
+    /// Creates an unnamed pair of connected sockets.
+    pub fn pair() -> io::Result<(Self, Self)> {
+        let mut fds = [0; 2];
+        cvt(unsafe {
+            libc::socketpair(AF_UNIX, SOCK_SEQPACKET | SOCK_FLAGS, 0, fds.as_mut_ptr())
+        })?;
+        let (a, b) = unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+        Ok((Self { fd: set_cloexec(a)? }, Self { fd: set_cloexec(b)? }))
+    }
+
+    /// Sends a message, returning the number of bytes sent.
+    ///
+    /// On platforms that support it, the `SIGPIPE` signal is suppressed if the peer has closed its
+    /// end of the connection; an error of the `BrokenPipe` kind is returned instead.
+    pub fn send(&self, buf: &[u8]) -> io::Result<usize> {
+        let ret =
+            unsafe { libc::send(self.fd.as_raw_fd(), buf.as_ptr().cast(), buf.len(), SEND_FLAGS) };
+        if ret < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(ret as usize)
+        }
+    }
+    /// Shuts down the read half, the write half or both halves of the connection.
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        let how = match how {
+            Shutdown::Read => libc::SHUT_RD,
+            Shutdown::Write => libc::SHUT_WR,
+            Shutdown::Both => libc::SHUT_RDWR,
+        };
+        cvt(unsafe { libc::shutdown(self.fd.as_raw_fd(), how) }).map(|_| ())
+    }
+
+    /// Returns the address that this socket is bound to.
+    pub fn local_addr(&self) -> io::Result<UnixAddr> {
+        sockname(self.fd.as_fd(), libc::getsockname)
+    }
+    /// Returns the address of the peer.
+    pub fn peer_addr(&self) -> io::Result<UnixAddr> {
+        sockname(self.fd.as_fd(), libc::getpeername)
+    }
+    /// Moves the socket into or out of nonblocking mode.
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        set_nonblocking(self.fd.as_fd(), nonblocking)
+    }
+}
+
+/// A listener for connections to [`UnixSeqpacket`] sockets.
+#[derive(Debug)]
+pub struct UnixSeqpacketListener {
+    fd: OwnedFd,
+}
+impl UnixSeqpacketListener {
+    /// Creates a listener bound to the given path.
+    pub fn bind(path: impl AsRef<Path>) -> io::Result<Self> {
+        let fd = socket()?;
+        let (addr, len) = sockaddr_from_path(path.as_ref())?;
+        cvt(unsafe { libc::bind(fd.as_raw_fd(), (&addr as *const sockaddr_un).cast(), len) })?;
+        cvt(unsafe { libc::listen(fd.as_raw_fd(), SOMAXCONN) })?;
+        Ok(Self { fd })
+    }
+    /// Accepts a connection, returning the connected socket and the address of the peer.
+    pub fn accept(&self) -> io::Result<(UnixSeqpacket, UnixAddr)> {
+        let mut storage = unsafe { zeroed::<sockaddr_storage>() };
+        let mut len = size_of::<sockaddr_storage>() as socklen_t;
+        let paddr = (&mut storage as *mut sockaddr_storage).cast();
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        let fd = unsafe { libc::accept4(self.fd.as_raw_fd(), paddr, &mut len, libc::SOCK_CLOEXEC) };
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        let fd = unsafe { libc::accept(self.fd.as_raw_fd(), paddr, &mut len) };
+        let fd = set_cloexec(unsafe { OwnedFd::from_raw_fd(cvt(fd)?) })?;
+        Ok((UnixSeqpacket { fd }, extract_unix_address(&storage, len)?))
+    }
+
+    /// Returns the address that this listener is bound to.
+    pub fn local_addr(&self) -> io::Result<UnixAddr> {
+        sockname(self.fd.as_fd(), libc::getsockname)
+    }
+    /// Moves the listener into or out of nonblocking mode.
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        set_nonblocking(self.fd.as_fd(), nonblocking)
+    }
+}
+
+fn set_nonblocking(fd: BorrowedFd<'_>, nonblocking: bool) -> io::Result<()> {
+    let flags = cvt(unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_GETFL) })?;
+    let flags = if nonblocking { flags | libc::O_NONBLOCK } else { flags & !libc::O_NONBLOCK };
+    cvt(unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_SETFL, flags) }).map(|_| ())
+}
+
+macro_rules! fd_conversions {
+    ($($ty:ident)+) => {$(
+        impl AsFd for $ty {
+            #[inline]
+            fn as_fd(&self) -> BorrowedFd<'_> {
+                self.fd.as_fd()
+            }
+        }
+        impl AsRawFd for $ty {
+            #[inline]
+            fn as_raw_fd(&self) -> RawFd {
+                self.fd.as_raw_fd()
+            }
+        }
+        impl IntoRawFd for $ty {
+            #[inline]
+            fn into_raw_fd(self) -> RawFd {
+                self.fd.into_raw_fd()
+            }
+        }
+        /// The file descriptor is assumed to be a Unix domain socket of the `SOCK_SEQPACKET` type.
+        impl FromRawFd for $ty {
+            #[inline]
+            unsafe fn from_raw_fd(fd: RawFd) -> Self {
+                Self { fd: unsafe { OwnedFd::from_raw_fd(fd) } }
+            }
+        }
+        impl From<$ty> for OwnedFd {
+            #[inline]
+            fn from(s: $ty) -> Self {
+                s.fd
+            }
+        }
+        /// The file descriptor is assumed to be a Unix domain socket of the `SOCK_SEQPACKET` type.
+        impl From<OwnedFd> for $ty {
+            #[inline]
+            fn from(fd: OwnedFd) -> Self {
+                Self { fd }
+            }
+        }
+    )+};
+}
+fd_conversions! { UnixSeqpacket UnixSeqpacketListener }
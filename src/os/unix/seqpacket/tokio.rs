@@ -0,0 +1,172 @@
+//! Tokio-based async versions of [`UnixSeqpacket`](super::UnixSeqpacket) and
+//! [`UnixSeqpacketListener`](super::UnixSeqpacketListener).
+
+use super::{set_nonblocking, sockaddr_from_path, socket};
+use crate::os::unix::getsockopt_int;
+use ::tokio::io::{unix::AsyncFd, Interest};
+use core::task::{Context, Poll};
+use libc::{sockaddr_un, EINPROGRESS, SOL_SOCKET, SO_ERROR};
+use std::{
+    io,
+    net::Shutdown,
+    os::{
+        fd::{AsFd, AsRawFd, BorrowedFd, RawFd},
+        unix::net::SocketAddr as UnixAddr,
+    },
+    path::Path,
+};
+
+/// Async version of [`os::unix::UnixSeqpacket`](super::UnixSeqpacket), registered with the Tokio
+/// reactor.
+#[derive(Debug)]
+pub struct UnixSeqpacket {
+    inner: AsyncFd<super::UnixSeqpacket>,
+}
+impl UnixSeqpacket {
+    /// Connects to the socket bound to the given path.
+    ///
+    /// On Linux, connecting to a listener whose backlog is full fails with an error of the
+    /// `WouldBlock` kind instead of waiting for room in the backlog.
+    pub async fn connect(path: impl AsRef<Path>) -> io::Result<Self> {
+        let fd = socket()?;
+        set_nonblocking(fd.as_fd(), true)?;
+        let (addr, len) = sockaddr_from_path(path.as_ref())?;
+        let slf = Self { inner: AsyncFd::new(super::UnixSeqpacket { fd })? };
+        let ret =
+            unsafe { libc::connect(slf.as_raw_fd(), (&addr as *const sockaddr_un).cast(), len) };
+        if ret != 0 {
+            // A full backlog on the listener is reported as EAGAIN, which is returned as is, just
+            // like Tokio's own Unix domain sockets do.
+            let e = io::Error::last_os_error();
+            if e.raw_os_error() != Some(EINPROGRESS) {
+                return Err(e);
+            }
+            slf.inner.writable().await?.retain_ready();
+            match getsockopt_int(slf.as_fd(), SOL_SOCKET, SO_ERROR)? {
+                0 => {}
+                e => return Err(io::Error::from_raw_os_error(e)),
+            }
+        }
+        Ok(slf)
+    }
+    /// Creates an unnamed pair of connected sockets.
+    pub fn pair() -> io::Result<(Self, Self)> {
+        let (a, b) = super::UnixSeqpacket::pair()?;
+        Ok((Self::from_std(a)?, Self::from_std(b)?))
+    }
+    /// Registers a synchronous socket with the Tokio reactor, moving it into nonblocking mode.
+    pub fn from_std(socket: super::UnixSeqpacket) -> io::Result<Self> {
+        socket.set_nonblocking(true)?;
+        Ok(Self { inner: AsyncFd::new(socket)? })
+    }
+    /// Deregisters the socket from the Tokio reactor, returning the synchronous socket, which is
+    /// left in nonblocking mode.
+    pub fn into_std(self) -> super::UnixSeqpacket {
+        self.inner.into_inner()
+    }
+    /// Borrows the synchronous socket.
+    #[inline]
+    pub fn get_ref(&self) -> &super::UnixSeqpacket {
+        self.inner.get_ref()
+    }
+
+    /// Sends a message, returning the number of bytes sent.
+    ///
+    /// See [`os::unix::UnixSeqpacket::send()`](super::UnixSeqpacket::send) for details.
+    pub async fn send(&self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.async_io(Interest::WRITABLE, |s| s.send(buf)).await
+    }
+    /// Shuts down the read half, the write half or both halves of the connection.
+    #[inline]
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        self.get_ref().shutdown(how)
+    }
+    /// Returns the address that this socket is bound to.
+    #[inline]
+    pub fn local_addr(&self) -> io::Result<UnixAddr> {
+        self.get_ref().local_addr()
+    }
+    /// Returns the address of the peer.
+    #[inline]
+    pub fn peer_addr(&self) -> io::Result<UnixAddr> {
+        self.get_ref().peer_addr()
+    }
+
+    /// Waits for the socket to become readable after a receive operation has found it not to be,
+    /// which makes the readiness that Tokio has cached for it stale.
+    pub(crate) fn poll_recv_ready(&self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.inner.poll_read_ready(cx) {
+            Poll::Ready(Ok(mut guard)) => {
+                guard.clear_ready();
+                Poll::Ready(Ok(()))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+impl AsFd for UnixSeqpacket {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.get_ref().as_fd()
+    }
+}
+impl AsRawFd for UnixSeqpacket {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        self.get_ref().as_raw_fd()
+    }
+}
+
+/// Async version of [`os::unix::UnixSeqpacketListener`](super::UnixSeqpacketListener), registered
+/// with the Tokio reactor.
+#[derive(Debug)]
+pub struct UnixSeqpacketListener {
+    inner: AsyncFd<super::UnixSeqpacketListener>,
+}
+impl UnixSeqpacketListener {
+    /// Creates a listener bound to the given path.
+    ///
+    /// Must be called from within the context of a Tokio runtime.
+    pub fn bind(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_std(super::UnixSeqpacketListener::bind(path)?)
+    }
+    /// Registers a synchronous listener with the Tokio reactor, moving it into nonblocking mode.
+    pub fn from_std(listener: super::UnixSeqpacketListener) -> io::Result<Self> {
+        listener.set_nonblocking(true)?;
+        Ok(Self { inner: AsyncFd::new(listener)? })
+    }
+    /// Deregisters the listener from the Tokio reactor, returning the synchronous listener, which
+    /// is left in nonblocking mode.
+    pub fn into_std(self) -> super::UnixSeqpacketListener {
+        self.inner.into_inner()
+    }
+    /// Borrows the synchronous listener.
+    #[inline]
+    pub fn get_ref(&self) -> &super::UnixSeqpacketListener {
+        self.inner.get_ref()
+    }
+
+    /// Accepts a connection, returning the connected socket and the address of the peer.
+    pub async fn accept(&self) -> io::Result<(UnixSeqpacket, UnixAddr)> {
+        let (socket, addr) = self.inner.async_io(Interest::READABLE, |l| l.accept()).await?;
+        Ok((UnixSeqpacket::from_std(socket)?, addr))
+    }
+    /// Returns the address that this listener is bound to.
+    #[inline]
+    pub fn local_addr(&self) -> io::Result<UnixAddr> {
+        self.get_ref().local_addr()
+    }
+}
+impl AsFd for UnixSeqpacketListener {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.get_ref().as_fd()
+    }
+}
+impl AsRawFd for UnixSeqpacketListener {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        self.get_ref().as_raw_fd()
+    }
+}
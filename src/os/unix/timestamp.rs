@@ -0,0 +1,153 @@
+use super::{
+    cmsg_value::{cbuf_size, recv_with_cmsg_value},
+    setsockopt_int, AsyncRecvMsgWithCmsg, Cmsg, RecvMsgWithCmsg, RecvWithCmsgValue,
+};
+use crate::{MsgBuf, RecvResult};
+use core::{mem::size_of, time::Duration};
+use libc::{
+    c_int, timespec, timeval, SCM_TIMESTAMP, SCM_TIMESTAMPING, SCM_TIMESTAMPNS,
+    SOF_TIMESTAMPING_RX_SOFTWARE, SOF_TIMESTAMPING_SOFTWARE, SOL_SOCKET, SO_TIMESTAMPING,
+    SO_TIMESTAMPNS,
+};
+use std::{
+    io,
+    os::fd::AsFd,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const TS_CBUF_SIZE: usize = cbuf_size(size_of::<[timespec; 3]>());
+
+/// A kernel receive timestamp, as received via `SCM_TIMESTAMPNS`, `SCM_TIMESTAMPING` or
+/// `SCM_TIMESTAMP`.
+///
+/// Measured against the realtime clock; convertible to [`SystemTime`].
+///
+/// Linux-only.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+    /// Seconds since the Unix epoch.
+    pub secs: i64,
+    /// Nanoseconds after `secs`, less than one billion.
+    pub nanos: u32,
+}
+#[allow(clippy::unnecessary_cast)] // time_t is not i64 everywhere
+impl Timestamp {
+    fn from_timespec(ts: timespec) -> Self {
+        Self { secs: ts.tv_sec as i64, nanos: ts.tv_nsec as u32 }
+    }
+    fn from_timeval(tv: timeval) -> Self {
+        Self { secs: tv.tv_sec as i64, nanos: tv.tv_usec as u32 * 1000 }
+    }
+    /// Converts to a [`SystemTime`].
+    #[inline]
+    pub fn to_system_time(self) -> SystemTime {
+        let d = Duration::new(self.secs.unsigned_abs(), 0);
+        let t = if self.secs >= 0 { UNIX_EPOCH + d } else { UNIX_EPOCH - d };
+        t + Duration::from_nanos(self.nanos.into())
+    }
+}
+impl From<Timestamp> for SystemTime {
+    #[inline]
+    fn from(ts: Timestamp) -> Self {
+        ts.to_system_time()
+    }
+}
+
+impl Cmsg<'_> {
+    /// Returns the receive timestamp if this is an `SCM_TIMESTAMPNS`, `SCM_TIMESTAMPING` or
+    /// `SCM_TIMESTAMP` message.
+    ///
+    /// For `SCM_TIMESTAMPING`, the software timestamp is preferred over the raw hardware one, and
+    /// `None` is returned if neither is present.
+    ///
+    /// Linux-only.
+    pub fn timestamp(&self) -> Option<Timestamp> {
+        // SAFETY: timespec and timeval are structs of integers
+        unsafe {
+            match (self.level, self.ty) {
+                (SOL_SOCKET, SCM_TIMESTAMPNS) => {
+                    self.read_data::<timespec>().map(Timestamp::from_timespec)
+                }
+                (SOL_SOCKET, SCM_TIMESTAMPING) => {
+                    let [sw, _, hw] = self.read_data::<[timespec; 3]>()?;
+                    [sw, hw]
+                        .into_iter()
+                        .find(|ts| ts.tv_sec != 0 || ts.tv_nsec != 0)
+                        .map(Timestamp::from_timespec)
+                }
+                (SOL_SOCKET, SCM_TIMESTAMP) => {
+                    self.read_data::<timeval>().map(Timestamp::from_timeval)
+                }
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Enables or disables the `SO_TIMESTAMPNS` socket option, which makes the system attach a
+/// nanosecond-precision receive timestamp to every message received from then on.
+///
+/// Linux-only.
+pub fn set_timestamp_ns(socket: impl AsFd, enable: bool) -> io::Result<()> {
+    setsockopt_int(socket.as_fd(), SOL_SOCKET, SO_TIMESTAMPNS, enable as c_int)
+}
+/// Enables or disables software receive timestamps via the `SO_TIMESTAMPING` socket option.
+///
+/// Enabling replaces any other `SO_TIMESTAMPING` flags that may have been set on the socket, and
+/// disabling clears all of them. Unix domain sockets are only timestamped with
+/// [`set_timestamp_ns()`].
+///
+/// Linux-only.
+pub fn set_timestamping_software(socket: impl AsFd, enable: bool) -> io::Result<()> {
+    let flags = if enable { SOF_TIMESTAMPING_RX_SOFTWARE | SOF_TIMESTAMPING_SOFTWARE } else { 0 };
+    setsockopt_int(socket.as_fd(), SOL_SOCKET, SO_TIMESTAMPING, flags as c_int)
+}
+
+/// Receiving kernel receive timestamps together with messages.
+///
+/// Requires timestamping to be enabled on the socket beforehand, which can be done via
+/// [`set_timestamp_ns()`] or [`set_timestamping_software()`].
+pub trait RecvMsgWithTimestamp: RecvMsgWithCmsg {
+    /// Receives one message using the given buffer, storing its receive timestamp in `ts`, or
+    /// `None` if there was none attached to the message.
+    ///
+    /// The control messages are received into a small buffer on the stack, which only grows if
+    /// control messages other than timestamps arrive. Any file descriptors received alongside the
+    /// timestamp are closed.
+    fn recv_msg_with_timestamp(
+        &mut self,
+        buf: &mut MsgBuf<'_>,
+        ts: &mut Option<Timestamp>,
+        abuf: Option<&mut Self::AddrBuf>,
+    ) -> Result<RecvResult, Self::Error> {
+        recv_with_cmsg_value::<_, _, TS_CBUF_SIZE>(self, buf, ts, abuf, |c| c.timestamp())
+    }
+}
+impl<T: RecvMsgWithCmsg + ?Sized> RecvMsgWithTimestamp for T {}
+
+/// Futures for receiving kernel receive timestamps together with messages.
+///
+/// This is the async version of [`RecvMsgWithTimestamp`].
+pub trait AsyncRecvMsgWithTimestampExt: AsyncRecvMsgWithCmsg {
+    /// Receives one message using the given buffer, storing its receive timestamp in `ts`.
+    ///
+    /// See [`RecvMsgWithTimestamp::recv_msg_with_timestamp()`] for details.
+    #[inline]
+    fn recv_msg_with_timestamp<'io, 'buf, 'slice: 'buf, 'ts, 'abuf>(
+        &'io mut self,
+        buf: &'buf mut MsgBuf<'slice>,
+        ts: &'ts mut Option<Timestamp>,
+        abuf: Option<&'abuf mut Self::AddrBuf>,
+    ) -> RecvWithTimestamp<'io, 'buf, 'slice, 'ts, 'abuf, Self>
+    where
+        Self: Unpin,
+    {
+        RecvWithCmsgValue::new(self, buf, ts, abuf, |c| c.timestamp())
+    }
+}
+impl<T: AsyncRecvMsgWithCmsg + ?Sized> AsyncRecvMsgWithTimestampExt for T {}
+
+/// Future type returned by
+/// [`.recv_msg_with_timestamp()`](AsyncRecvMsgWithTimestampExt::recv_msg_with_timestamp).
+pub type RecvWithTimestamp<'io, 'buf, 'slice, 'ts, 'abuf, RM> =
+    RecvWithCmsgValue<'io, 'buf, 'slice, 'ts, 'abuf, RM, Timestamp, TS_CBUF_SIZE>;
@@ -0,0 +1,333 @@
+use super::CmsgBuf;
+use crate::{
+    GrowError, MsgBuf, OversizePolicy, QuotaExceeded, RecvMsg, RecvResult, TruncatingRecvMsg,
+};
+use alloc::vec::Vec;
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use std::os::fd::OwnedFd;
+
+/// Receiving from socket-like connections with message boundaries together with ancillary data,
+/// with truncation detection.
+pub trait TruncatingRecvMsgWithCmsg: TruncatingRecvMsg {
+    /// Like [`.recv_trunc()`](TruncatingRecvMsg::recv_trunc), but also receives the control
+    /// messages attached to the message into `cbuf`.
+    ///
+    /// Truncation of the control messages is reported by
+    /// [`cbuf.is_truncated()`](CmsgBuf::is_truncated) rather than by the return value.
+    ///
+    /// # Contract notes
+    /// The contract notes of `.recv_trunc()` apply, and additionally:
+    /// - **Must not** affect the capacity of `cbuf`.
+    fn recv_trunc_with_cmsg(
+        &mut self,
+        peek: bool,
+        buf: &mut MsgBuf<'_>,
+        cbuf: &mut CmsgBuf<'_>,
+        abuf: Option<&mut Self::AddrBuf>,
+    ) -> Result<Option<bool>, Self::Error>;
+}
+fn _assert_object_safe_trmwc<E, AB, TRM: TruncatingRecvMsgWithCmsg<Error = E, AddrBuf = AB>>(
+    x: &TRM,
+) -> &(dyn TruncatingRecvMsgWithCmsg<Error = E, AddrBuf = AB> + '_) {
+    x
+}
+
+/// Receiving from socket-like connections with message boundaries together with ancillary data,
+/// without truncation of either.
+pub trait RecvMsgWithCmsg: RecvMsg {
+    /// Like [`.recv_msg()`](RecvMsg::recv_msg), but also receives the control messages attached to
+    /// the message into `cbuf`, (re)allocating it if necessary.
+    ///
+    /// `RecvResult::Spilled` is returned if `buf` had to be grown; growth of `cbuf` alone does not
+    /// count as spilling. `RecvResult::QuotaExceeded` is returned if either of the buffers reached
+    /// its quota, and `RecvResult::AllocFailed` if growing either of them failed. The
+    /// [oversize policy](OversizePolicy) of `buf` applies to both buffers; with
+    /// `OversizePolicy::Truncate`, `RecvResult::Truncated` is returned if either of them ended up
+    /// truncated.
+    fn recv_msg_with_cmsg(
+        &mut self,
+        buf: &mut MsgBuf<'_>,
+        cbuf: &mut CmsgBuf<'_>,
+        abuf: Option<&mut Self::AddrBuf>,
+    ) -> Result<RecvResult, Self::Error>;
+}
+fn _assert_object_safe_rmwc<E, AB, RM: RecvMsgWithCmsg<Error = E, AddrBuf = AB>>(
+    x: &RM,
+) -> &(dyn RecvMsgWithCmsg<Error = E, AddrBuf = AB> + '_) {
+    x
+}
+
+/// Receiving file descriptors sent via `SCM_RIGHTS` together with messages.
+pub trait RecvMsgWithFds: RecvMsgWithCmsg {
+    /// Receives one message using the given buffers, then takes the file descriptors that came
+    /// with it out of `cbuf` and appends them to `fds`.
+    ///
+    /// If the control messages do not fit into `cbuf`, it is grown, and the descriptors received in
+    /// the meantime are closed; should the quota of `cbuf` be reached instead, no descriptors are
+    /// taken and the message is left unreceived. Control messages of other types stay in `cbuf`.
+    ///
+    /// The descriptors are received with the close-on-exec flag set.
+    fn recv_msg_with_fds(
+        &mut self,
+        buf: &mut MsgBuf<'_>,
+        cbuf: &mut CmsgBuf<'_>,
+        fds: &mut Vec<OwnedFd>,
+        abuf: Option<&mut Self::AddrBuf>,
+    ) -> Result<RecvResult, Self::Error> {
+        let rslt = self.recv_msg_with_cmsg(buf, cbuf, abuf)?;
+        if matches!(rslt, RecvResult::Fit | RecvResult::Spilled) {
+            cbuf.take_fds(fds);
+        }
+        Ok(rslt)
+    }
+}
+impl<T: RecvMsgWithCmsg + ?Sized> RecvMsgWithFds for T {}
+
+/// Implements [`RecvMsgWithCmsg::recv_msg_with_cmsg()`] via
+/// [`TruncatingRecvMsgWithCmsg::recv_trunc_with_cmsg()`].
+///
+/// The message is peeked with both buffers growing until neither of them is truncated, and is then
+/// received into them without peeking. The peeks only serve to size the buffers: the control
+/// messages they produce are discarded, closing any file descriptors that came with them, and the
+/// ones that end up in `cbuf` are those of the final reception. This also makes it work on systems
+/// that don't pass file descriptors along with peeked messages.
+pub fn recv_with_cmsg_via_recv_trunc_with_cmsg<TRM: TruncatingRecvMsgWithCmsg + ?Sized>(
+    slf: &mut TRM,
+    buf: &mut MsgBuf<'_>,
+    cbuf: &mut CmsgBuf<'_>,
+    mut abuf: Option<&mut TRM::AddrBuf>,
+) -> Result<RecvResult, TRM::Error> {
+    // Only tracks the growth of `buf`
+    let mut spilled = false;
+    loop {
+        let fit = match slf.recv_trunc_with_cmsg(true, buf, cbuf, abuf.as_deref_mut()) {
+            Ok(Some(fit)) => fit,
+            Ok(None) => return Ok(RecvResult::EndOfStream),
+            Err(e) => {
+                buf.set_fill(0);
+                buf.has_msg = false;
+                cbuf.clear();
+                return Err(e);
+            }
+        };
+        let cfit = !cbuf.is_truncated();
+        cbuf.clear();
+        if fit && cfit {
+            break;
+        }
+        spilled |= !fit;
+        let grown = if fit { Ok(()) } else { buf.try_clear_and_grow() }.and_then(|()| {
+            if cfit {
+                Ok(())
+            } else {
+                cbuf.try_clear_and_grow()
+            }
+        });
+        match grown {
+            Ok(()) => {}
+            Err(GrowError::QuotaExceeded(qe)) => {
+                return recv_with_cmsg_oversize(slf, buf, cbuf, abuf, (fit, cfit), spilled, qe);
+            }
+            Err(e) => {
+                buf.set_fill(0);
+                buf.has_msg = false;
+                return Ok(e.into());
+            }
+        }
+    }
+    match slf.recv_trunc_with_cmsg(false, buf, cbuf, abuf) {
+        Ok(Some(fit)) => {
+            let rslt = if !fit || cbuf.is_truncated() {
+                // Only if the system reported less than it delivers when peeking
+                RecvResult::Truncated { full_len: None }
+            } else if spilled {
+                RecvResult::Spilled
+            } else {
+                RecvResult::Fit
+            };
+            Ok(buf.after_recv(rslt))
+        }
+        Ok(None) => Ok(RecvResult::EndOfStream),
+        Err(e) => {
+            buf.set_fill(0);
+            buf.has_msg = false;
+            cbuf.clear();
+            Err(e)
+        }
+    }
+}
+
+/// Applies the [oversize policy](OversizePolicy) of `buf` to the message at the front of the
+/// queue, which did not fit into either `buf` or `cbuf` within their quotas. `fits` records which
+/// of the two the last peek fit into, and `spilled` whether `buf` has been grown already.
+fn recv_with_cmsg_oversize<TRM: TruncatingRecvMsgWithCmsg + ?Sized>(
+    slf: &mut TRM,
+    buf: &mut MsgBuf<'_>,
+    cbuf: &mut CmsgBuf<'_>,
+    abuf: Option<&mut TRM::AddrBuf>,
+    (fit, cfit): (bool, bool),
+    spilled: bool,
+    qe: QuotaExceeded,
+) -> Result<RecvResult, TRM::Error> {
+    buf.set_fill(0);
+    buf.has_msg = false;
+    cbuf.clear();
+    match buf.oversize {
+        OversizePolicy::LeaveQueued => {}
+        OversizePolicy::Discard => slf.discard_msg()?,
+        OversizePolicy::Truncate => {
+            let grown =
+                if fit { Ok(()) } else { buf.try_clear_and_grow_to_quota() }.and_then(|()| {
+                    if cfit {
+                        Ok(())
+                    } else {
+                        cbuf.try_clear_and_grow_to_quota()
+                    }
+                });
+            if let Err(e) = grown {
+                return Ok(e.into());
+            }
+            return match slf.recv_trunc_with_cmsg(false, buf, cbuf, abuf) {
+                Ok(Some(true)) if !cbuf.is_truncated() => {
+                    let rslt = if spilled || !fit { RecvResult::Spilled } else { RecvResult::Fit };
+                    Ok(buf.after_recv(rslt))
+                }
+                Ok(Some(..)) => Ok(buf.after_recv(RecvResult::Truncated { full_len: None })),
+                Ok(None) => Ok(RecvResult::EndOfStream),
+                Err(e) => {
+                    buf.set_fill(0);
+                    buf.has_msg = false;
+                    cbuf.clear();
+                    Err(e)
+                }
+            };
+        }
+    }
+    Ok(RecvResult::QuotaExceeded(qe))
+}
+
+/// Asynchronously receiving from socket-like connections with message boundaries together with
+/// ancillary data, without truncation of either.
+///
+/// This is the async version of [`RecvMsgWithCmsg`].
+pub trait AsyncRecvMsgWithCmsg: crate::AsyncRecvMsg {
+    /// Polls a future that receives one message using the given buffer together with its control
+    /// messages, (re)allocating the buffers if necessary.
+    ///
+    /// See [`RecvMsgWithCmsg::recv_msg_with_cmsg()`] for details.
+    fn poll_recv_msg_with_cmsg(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut MsgBuf<'_>,
+        cbuf: &mut CmsgBuf<'_>,
+        abuf: Option<&mut Self::AddrBuf>,
+    ) -> Poll<Result<RecvResult, Self::Error>>;
+}
+fn _assert_object_safe_armwc<E, AB, RM: AsyncRecvMsgWithCmsg<Error = E, AddrBuf = AB>>(
+    x: &RM,
+) -> &(dyn AsyncRecvMsgWithCmsg<Error = E, AddrBuf = AB> + '_) {
+    x
+}
+
+/// Futures for [`AsyncRecvMsgWithCmsg`].
+pub trait AsyncRecvMsgWithCmsgExt: AsyncRecvMsgWithCmsg {
+    /// Receives one message using the given buffer together with its control messages,
+    /// (re)allocating the buffers if necessary.
+    ///
+    /// See [`RecvMsgWithCmsg::recv_msg_with_cmsg()`] for details.
+    #[inline]
+    fn recv_msg_with_cmsg<'io, 'buf, 'slice: 'buf, 'cbuf, 'cslice: 'cbuf, 'abuf>(
+        &'io mut self,
+        buf: &'buf mut MsgBuf<'slice>,
+        cbuf: &'cbuf mut CmsgBuf<'cslice>,
+        abuf: Option<&'abuf mut Self::AddrBuf>,
+    ) -> RecvWithCmsg<'io, 'buf, 'slice, 'cbuf, 'cslice, 'abuf, Self>
+    where
+        Self: Unpin,
+    {
+        RecvWithCmsg { recver: self, buf, cbuf, abuf }
+    }
+    /// Receives one message using the given buffers, then takes the file descriptors that came
+    /// with it out of `cbuf` and appends them to `fds`.
+    ///
+    /// See [`RecvMsgWithFds::recv_msg_with_fds()`] for details.
+    #[inline]
+    fn recv_msg_with_fds<'io, 'buf, 'slice: 'buf, 'cbuf, 'cslice: 'cbuf, 'fds, 'abuf>(
+        &'io mut self,
+        buf: &'buf mut MsgBuf<'slice>,
+        cbuf: &'cbuf mut CmsgBuf<'cslice>,
+        fds: &'fds mut Vec<OwnedFd>,
+        abuf: Option<&'abuf mut Self::AddrBuf>,
+    ) -> RecvWithFds<'io, 'buf, 'slice, 'cbuf, 'cslice, 'fds, 'abuf, Self>
+    where
+        Self: Unpin,
+    {
+        RecvWithFds { recver: self, buf, cbuf, fds, abuf }
+    }
+}
+impl<T: AsyncRecvMsgWithCmsg + ?Sized> AsyncRecvMsgWithCmsgExt for T {}
+
+/// Future type returned by
+/// [`.recv_msg_with_cmsg()`](AsyncRecvMsgWithCmsgExt::recv_msg_with_cmsg).
+#[derive(Debug)]
+pub struct RecvWithCmsg<'io, 'buf, 'slice, 'cbuf, 'cslice, 'abuf, RM: AsyncRecvMsgWithCmsg + ?Sized>
+{
+    recver: &'io mut RM,
+    buf: &'buf mut MsgBuf<'slice>,
+    cbuf: &'cbuf mut CmsgBuf<'cslice>,
+    abuf: Option<&'abuf mut RM::AddrBuf>,
+}
+impl<RM: AsyncRecvMsgWithCmsg + Unpin + ?Sized> Future
+    for RecvWithCmsg<'_, '_, '_, '_, '_, '_, RM>
+{
+    type Output = Result<RecvResult, RM::Error>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let RecvWithCmsg { recver, buf, cbuf, abuf } = self.get_mut();
+        Pin::new(&mut **recver).poll_recv_msg_with_cmsg(cx, buf, cbuf, abuf.as_deref_mut())
+    }
+}
+
+/// Future type returned by
+/// [`.recv_msg_with_fds()`](AsyncRecvMsgWithCmsgExt::recv_msg_with_fds).
+#[derive(Debug)]
+pub struct RecvWithFds<
+    'io,
+    'buf,
+    'slice,
+    'cbuf,
+    'cslice,
+    'fds,
+    'abuf,
+    RM: AsyncRecvMsgWithCmsg + ?Sized,
+> {
+    recver: &'io mut RM,
+    buf: &'buf mut MsgBuf<'slice>,
+    cbuf: &'cbuf mut CmsgBuf<'cslice>,
+    fds: &'fds mut Vec<OwnedFd>,
+    abuf: Option<&'abuf mut RM::AddrBuf>,
+}
+impl<RM: AsyncRecvMsgWithCmsg + Unpin + ?Sized> Future
+    for RecvWithFds<'_, '_, '_, '_, '_, '_, '_, RM>
+{
+    type Output = Result<RecvResult, RM::Error>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let RecvWithFds { recver, buf, cbuf, fds, abuf } = self.get_mut();
+        let rslt = match Pin::new(&mut **recver).poll_recv_msg_with_cmsg(
+            cx,
+            buf,
+            cbuf,
+            abuf.as_deref_mut(),
+        ) {
+            Poll::Ready(r) => r?,
+            Poll::Pending => return Poll::Pending,
+        };
+        if matches!(rslt, RecvResult::Fit | RecvResult::Spilled) {
+            cbuf.take_fds(fds);
+        }
+        Poll::Ready(Ok(rslt))
+    }
+}
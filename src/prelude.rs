@@ -2,13 +2,18 @@
 pub use crate::{
     msgbuf::MsgBuf,
     r#async::{
-        RecvMsg as AsyncRecvMsg, RecvMsgExt as AsyncRecvMsgExt,
+        RecvMsg as AsyncRecvMsg, RecvMsgBatch as AsyncRecvMsgBatch,
+        RecvMsgBatchExt as AsyncRecvMsgBatchExt, RecvMsgExt as AsyncRecvMsgExt,
+        RecvMsgVectored as AsyncRecvMsgVectored, RecvMsgVectoredExt as AsyncRecvMsgVectoredExt,
         TruncatingRecvMsg as AsyncTruncatingRecvMsg,
         TruncatingRecvMsgExt as AsyncTruncatingRecvMsgExt,
+        TruncatingRecvMsgVectored as AsyncTruncatingRecvMsgVectored,
+        TruncatingRecvMsgVectoredExt as AsyncTruncatingRecvMsgVectoredExt,
         TruncatingRecvMsgWithFullSize as AsyncTruncatingRecvMsgWithFullSize,
         TruncatingRecvMsgWithFullSizeExt as AsyncTruncatingRecvMsgWithFullSizeExt,
     },
     sync::{
-        RecvMsg, TruncatingRecvMsg, TruncatingRecvMsgWithFullSize, TruncatingRecvMsgWithFullSizeExt,
+        RecvMsg, RecvMsgBatch, RecvMsgVectored, TruncatingRecvMsg, TruncatingRecvMsgVectored,
+        TruncatingRecvMsgWithFullSize, TruncatingRecvMsgWithFullSizeExt,
     },
 };
@@ -14,11 +14,11 @@ pub(crate) mod r#impl {
         pub(crate) mod windows;
 
         #[cfg(test)]
-        mod tests;
+        pub(crate) mod tests;
     }
 }
 
-use crate::{MsgBuf, RecvResult, TryRecvResult};
+use crate::{MsgBatch, MsgBuf, RecvResult, TryRecvResult};
 
 /// Receiving from socket-like connections with message boundaries with truncation detection.
 pub trait TruncatingRecvMsg {
@@ -92,6 +92,32 @@ fn _assert_object_safe_trmwfs<
     x
 }
 
+/// Like [`TruncatingRecvMsg`], but scatters each message across multiple buffers.
+pub trait TruncatingRecvMsgVectored: TruncatingRecvMsg {
+    /// Like [`.recv_trunc()`](TruncatingRecvMsg::recv_trunc), but receives one message into the
+    /// sequence of buffers given by `bufs`, filling each one to capacity before moving on to the
+    /// next. Truncation means that the message did not fit into the buffers combined.
+    ///
+    /// # Contract notes
+    /// The contract notes of `.recv_trunc()` apply to every buffer in `bufs`, with the fill cursor
+    /// of each one set to the part of the message that was received into it. Buffers that the
+    /// message did not reach are given a fill cursor of zero.
+    ///
+    /// # Panics
+    /// May panic if `bufs` is empty.
+    fn recv_trunc_vectored(
+        &mut self,
+        peek: bool,
+        bufs: &mut [MsgBuf<'_>],
+        abuf: Option<&mut Self::AddrBuf>,
+    ) -> Result<Option<bool>, Self::Error>;
+}
+fn _assert_object_safe_trmv<E, AB, TRMV: TruncatingRecvMsgVectored<Error = E, AddrBuf = AB>>(
+    x: &TRMV,
+) -> &(dyn TruncatingRecvMsgVectored<Error = E, AddrBuf = AB> + '_) {
+    x
+}
+
 /// Receiving from socket-like connections with message boundaries without truncation.
 pub trait RecvMsg {
     /// The I/O error type.
@@ -122,3 +148,59 @@ fn _assert_object_safe_rm<E, AB, RM: RecvMsg<Error = E, AddrBuf = AB>>(
 ) -> &(dyn RecvMsg<Error = E, AddrBuf = AB> + '_) {
     x
 }
+
+/// Like [`RecvMsg`], but scatters each message across multiple buffers.
+pub trait RecvMsgVectored: RecvMsg {
+    /// Receives one message into the sequence of buffers given by `bufs`, (re)allocating the last
+    /// buffer if necessary. All buffers except for the last one are used as-is, so their capacity
+    /// can be relied upon to stay the same, as can their address (which makes it possible to, for
+    /// instance, receive a fixed-size header into an aligned buffer on the stack).
+    ///
+    /// The fill cursor of each buffer is set to the part of the message that was received into it.
+    /// The [`RecvResult`] describes the last buffer; `Fit` means that the message fit into the
+    /// buffers without growing the last one.
+    ///
+    /// In the `Ok(..)` case, if `abuf` is `Some(..)`, it is filled with the address of the sender.
+    ///
+    /// If the operation could not be completed for external reasons, an error from the outermost
+    /// `Result` is returned.
+    ///
+    /// # Panics
+    /// May panic if `bufs` is empty.
+    fn recv_msg_vectored(
+        &mut self,
+        bufs: &mut [MsgBuf<'_>],
+        abuf: Option<&mut Self::AddrBuf>,
+    ) -> Result<RecvResult, Self::Error>;
+}
+fn _assert_object_safe_rmv<E, AB, RMV: RecvMsgVectored<Error = E, AddrBuf = AB>>(
+    x: &RMV,
+) -> &(dyn RecvMsgVectored<Error = E, AddrBuf = AB> + '_) {
+    x
+}
+
+/// Receiving multiple messages in one call from socket-like connections with message boundaries.
+pub trait RecvMsgBatch: RecvMsg {
+    /// Receives one or more messages into the buffers of `batch`, (re)allocating them if necessary.
+    ///
+    /// See [`MsgBatch`] for when messages can end up truncated.
+    ///
+    /// Waits for the first message just like [`.recv_msg()`](RecvMsg::recv_msg) does, then receives
+    /// more messages for as long as they are immediately available and there are buffers left. The
+    /// return value is the new [`.len()`](MsgBatch::len) of the batch, and the result for each
+    /// buffer is available via [`.results()`](MsgBatch::results).
+    ///
+    /// An error that occurs after the first message has been received ends the batch, and is
+    /// returned by the next call instead, so that no received messages are lost. This includes
+    /// failure to convert the sender address of a message, in which case the message is still
+    /// reported but its address buffer is left unchanged.
+    fn recv_msg_batch(
+        &mut self,
+        batch: &mut MsgBatch<'_, Self::AddrBuf>,
+    ) -> Result<usize, Self::Error>;
+}
+fn _assert_object_safe_rmb<E, AB, RMB: RecvMsgBatch<Error = E, AddrBuf = AB>>(
+    x: &RMB,
+) -> &(dyn RecvMsgBatch<Error = E, AddrBuf = AB> + '_) {
+    x
+}
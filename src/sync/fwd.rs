@@ -49,6 +49,27 @@ impl<T: TruncatingRecvMsgWithFullSize + ?Sized> TruncatingRecvMsgWithFullSize fo
     }
 }
 
+impl<T: TruncatingRecvMsgVectored + ?Sized> TruncatingRecvMsgVectored for &mut T {
+    forward_trait_methods! {
+        fn recv_trunc_vectored(
+            &mut self,
+            peek: bool,
+            bufs: &mut [MsgBuf<'_>],
+            abuf: Option<&mut Self::AddrBuf>,
+        ) -> Result<Option<bool>, Self::Error>;
+    }
+}
+impl<T: TruncatingRecvMsgVectored + ?Sized> TruncatingRecvMsgVectored for Box<T> {
+    forward_trait_methods! {
+        fn recv_trunc_vectored(
+            &mut self,
+            peek: bool,
+            bufs: &mut [MsgBuf<'_>],
+            abuf: Option<&mut Self::AddrBuf>,
+        ) -> Result<Option<bool>, Self::Error>;
+    }
+}
+
 impl<T: RecvMsg + ?Sized> RecvMsg for &mut T {
     type Error = T::Error;
     type AddrBuf = T::AddrBuf;
@@ -71,3 +92,39 @@ impl<T: RecvMsg + ?Sized> RecvMsg for alloc::boxed::Box<T> {
         ) -> Result<RecvResult, Self::Error>;
     }
 }
+
+impl<T: RecvMsgVectored + ?Sized> RecvMsgVectored for &mut T {
+    forward_trait_methods! {
+        fn recv_msg_vectored(
+            &mut self,
+            bufs: &mut [MsgBuf<'_>],
+            abuf: Option<&mut Self::AddrBuf>,
+        ) -> Result<RecvResult, Self::Error>;
+    }
+}
+impl<T: RecvMsgVectored + ?Sized> RecvMsgVectored for Box<T> {
+    forward_trait_methods! {
+        fn recv_msg_vectored(
+            &mut self,
+            bufs: &mut [MsgBuf<'_>],
+            abuf: Option<&mut Self::AddrBuf>,
+        ) -> Result<RecvResult, Self::Error>;
+    }
+}
+
+impl<T: RecvMsgBatch + ?Sized> RecvMsgBatch for &mut T {
+    forward_trait_methods! {
+        fn recv_msg_batch(
+            &mut self,
+            batch: &mut MsgBatch<'_, Self::AddrBuf>,
+        ) -> Result<usize, Self::Error>;
+    }
+}
+impl<T: RecvMsgBatch + ?Sized> RecvMsgBatch for Box<T> {
+    forward_trait_methods! {
+        fn recv_msg_batch(
+            &mut self,
+            batch: &mut MsgBatch<'_, Self::AddrBuf>,
+        ) -> Result<usize, Self::Error>;
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{MsgBuf, RecvMsg, RecvResult};
+use crate::{MsgBuf, RecvMsg, RecvResult, WithMaxMsgSize};
 use std::{
     mem::MaybeUninit,
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddrV4, UdpSocket},
@@ -60,3 +60,818 @@ the memory allocator decides to be smarter than usual and give us a huge buffer
     assert_eq!(abuf1.port(), p2);
     assert_eq!(abuf2.port(), p1);
 }
+
+#[test]
+fn max_msg_size() {
+    let addr = Ipv4Addr::LOCALHOST;
+    let s1 = UdpSocket::bind((addr, 0)).expect("first bind failed");
+    let s2 = UdpSocket::bind((addr, 0)).expect("second bind failed");
+    s1.connect(s2.local_addr().expect("address query failed")).expect("connect failed");
+    let mut recver = WithMaxMsgSize::new(&s2, 65535);
+
+    let msg = b"Received in one go";
+    let mut buf = MsgBuf::from(Vec::with_capacity(65535));
+    s1.send(msg).expect("first send failed");
+    let rslt = recver.recv_msg(&mut buf, None).expect("first receive failed");
+    assert!(matches!(rslt, RecvResult::Fit));
+    assert_eq!(buf.filled_part(), msg);
+
+    // Buffers below the maximum size still get peeked into and grown.
+    let mut bufa = [MaybeUninit::new(0); 6];
+    let mut buf = MsgBuf::from(bufa.as_mut());
+    s1.send(msg).expect("second send failed");
+    let rslt = recver.recv_msg(&mut buf, None).expect("second receive failed");
+    assert!(matches!(rslt, RecvResult::Spilled));
+    assert_eq!(buf.filled_part(), msg);
+
+    // A message exceeding the declared maximum is reported rather than panicking.
+    let mut recver = WithMaxMsgSize::new(&s2, 8);
+    let mut bufa = [MaybeUninit::new(0); 8];
+    let mut buf = MsgBuf::from(bufa.as_mut());
+    s1.send(msg).expect("third send failed");
+    let rslt = recver.recv_msg(&mut buf, None).expect("third receive failed");
+    assert!(matches!(rslt, RecvResult::Truncated { full_len: None }));
+    assert_eq!(buf.filled_part(), &msg[..8]);
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn single_call() {
+    use crate::{
+        sync::{recv_via_try_recv, recv_via_try_recv_with_max_size},
+        TruncatingRecvMsg, TruncatingRecvMsgWithFullSize, TryRecvResult,
+    };
+    use std::net::SocketAddr;
+
+    /// Counts receive calls.
+    struct Counting<'a>(&'a UdpSocket, usize);
+    impl TruncatingRecvMsg for Counting<'_> {
+        type Error = std::io::Error;
+        type AddrBuf = SocketAddr;
+        fn recv_trunc(
+            &mut self,
+            peek: bool,
+            buf: &mut MsgBuf<'_>,
+            abuf: Option<&mut SocketAddr>,
+        ) -> std::io::Result<Option<bool>> {
+            self.1 += 1;
+            self.0.recv_trunc(peek, buf, abuf)
+        }
+    }
+    impl TruncatingRecvMsgWithFullSize for Counting<'_> {
+        fn recv_trunc_with_full_size(
+            &mut self,
+            peek: bool,
+            buf: &mut MsgBuf<'_>,
+            abuf: Option<&mut SocketAddr>,
+        ) -> std::io::Result<TryRecvResult> {
+            self.1 += 1;
+            self.0.recv_trunc_with_full_size(peek, buf, abuf)
+        }
+    }
+
+    let addr = Ipv4Addr::LOCALHOST;
+    let s1 = UdpSocket::bind((addr, 0)).expect("first bind failed");
+    let s2 = UdpSocket::bind((addr, 0)).expect("second bind failed");
+    s1.connect(s2.local_addr().expect("address query failed")).expect("connect failed");
+    let msg = b"Received in one go";
+    let mut buf = MsgBuf::from(Vec::with_capacity(65535));
+
+    let mut recver = Counting(&s2, 0);
+    s1.send(msg).expect("send failed");
+    let rslt = recv_via_try_recv(&mut recver, &mut buf, None).expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Fit));
+    assert_eq!(recver.1, 2, "peek and discard");
+
+    let mut recver = Counting(&s2, 0);
+    s1.send(msg).expect("send failed");
+    let rslt = recv_via_try_recv_with_max_size(&mut recver, &mut buf, None, 65535)
+        .expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Fit));
+    assert_eq!(buf.filled_part(), msg);
+    assert_eq!(recver.1, 1);
+
+    // Truncation despite the declared maximum reports the full size.
+    let mut recver = Counting(&s2, 0);
+    let mut bufa = [MaybeUninit::new(0); 8];
+    let mut buf = MsgBuf::from(bufa.as_mut());
+    s1.send(msg).expect("send failed");
+    let rslt =
+        recv_via_try_recv_with_max_size(&mut recver, &mut buf, None, 8).expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Truncated { full_len: Some(18) }));
+    assert_eq!(buf.filled_part(), &msg[..8]);
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn try_recv_race() {
+    use crate::{
+        sync::recv_via_try_recv, TruncatingRecvMsg, TruncatingRecvMsgWithFullSize, TryRecvResult,
+    };
+    use std::net::SocketAddr;
+
+    /// Has another receiver take the message off the queue just before it's received.
+    struct Racing<'a>(&'a UdpSocket);
+    impl TruncatingRecvMsg for Racing<'_> {
+        type Error = std::io::Error;
+        type AddrBuf = SocketAddr;
+        fn recv_trunc(
+            &mut self,
+            peek: bool,
+            buf: &mut MsgBuf<'_>,
+            abuf: Option<&mut SocketAddr>,
+        ) -> std::io::Result<Option<bool>> {
+            self.0.recv_trunc(peek, buf, abuf)
+        }
+    }
+    impl TruncatingRecvMsgWithFullSize for Racing<'_> {
+        fn recv_trunc_with_full_size(
+            &mut self,
+            peek: bool,
+            buf: &mut MsgBuf<'_>,
+            abuf: Option<&mut SocketAddr>,
+        ) -> std::io::Result<TryRecvResult> {
+            if !peek {
+                self.0.recv(&mut [0; 64])?;
+            }
+            self.0.recv_trunc_with_full_size(peek, buf, abuf)
+        }
+    }
+
+    let addr = Ipv4Addr::LOCALHOST;
+    let s1 = UdpSocket::bind((addr, 0)).expect("first bind failed");
+    let s2 = UdpSocket::bind((addr, 0)).expect("second bind failed");
+    s1.connect(s2.local_addr().expect("address query failed")).expect("connect failed");
+    let msg = b"Taken by someone else";
+    let next = [0x55; 1024];
+    s1.send(msg).expect("send failed");
+    s1.send(&next).expect("send failed");
+
+    let mut buf = MsgBuf::from(Vec::new());
+    let rslt = recv_via_try_recv(&mut Racing(&s2), &mut buf, None).expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Truncated { full_len: Some(l) } if l == next.len()));
+    assert_eq!(buf.filled_part(), &next[..buf.capacity()]);
+}
+
+#[cfg(unix)]
+#[test]
+fn vectored() {
+    #[repr(C, align(8))]
+    struct Header([MaybeUninit<u8>; 8]);
+
+    let addr = Ipv4Addr::LOCALHOST;
+    let s1 = UdpSocket::bind((addr, 0)).expect("first bind failed");
+    let s2 = UdpSocket::bind((addr, 0)).expect("second bind failed");
+    s1.connect(s2.local_addr().expect("address query failed")).expect("connect failed");
+
+    let msg = b"HEADER!!and a payload that doesn't fit into the small buffer";
+    s1.send(msg).expect("send failed");
+
+    let mut hdr = Header([MaybeUninit::new(0); 8]);
+    let mut bufa = [MaybeUninit::new(0); 6];
+    let mut bufs = [MsgBuf::from(hdr.0.as_mut()), MsgBuf::from(bufa.as_mut())];
+    let rslt = crate::RecvMsgVectored::recv_msg_vectored(&mut &s2, &mut bufs, None)
+        .expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Spilled));
+    assert_eq!(bufs[0].capacity(), 8);
+    assert_eq!(bufs[0].filled_part(), &msg[..8]);
+    assert_eq!(bufs[1].filled_part(), &msg[8..]);
+}
+
+#[cfg(unix)]
+#[test]
+fn vectored_many() {
+    let addr = Ipv4Addr::LOCALHOST;
+    let s1 = UdpSocket::bind((addr, 0)).expect("first bind failed");
+    let s2 = UdpSocket::bind((addr, 0)).expect("second bind failed");
+    s1.connect(s2.local_addr().expect("address query failed")).expect("connect failed");
+
+    // More buffers than there is room for iovecs for on the stack
+    let msg = b"forty bytes spread across a dozen buffer";
+    s1.send(msg).expect("send failed");
+
+    let mut bufas = [[MaybeUninit::new(0); 4]; 12];
+    let mut bufs = bufas.iter_mut().map(|a| MsgBuf::from(a.as_mut())).collect::<Vec<_>>();
+    let rslt =
+        crate::TruncatingRecvMsgVectored::recv_trunc_vectored(&mut &s2, false, &mut bufs, None)
+            .expect("receive failed");
+    assert_eq!(rslt, Some(true));
+    let recved = bufs.iter().flat_map(|b| b.filled_part().iter().copied()).collect::<Vec<_>>();
+    assert_eq!(recved, msg);
+}
+
+#[cfg(unix)]
+#[test]
+fn batch() {
+    use crate::{MsgBatch, RecvMsgBatch};
+
+    let addr = Ipv4Addr::LOCALHOST;
+    let s1 = UdpSocket::bind((addr, 0)).expect("first bind failed");
+    let s2 = UdpSocket::bind((addr, 0)).expect("second bind failed");
+    s1.connect(s2.local_addr().expect("address query failed")).expect("connect failed");
+    let p1 = s1.local_addr().expect("address query failed").port();
+    let msgs: [&[u8]; 3] = [b"first", b"second, which is longer than the first", b"third"];
+
+    // The last two buffers are too small for the second message.
+    let mut batch = MsgBatch::with_addrs(
+        vec![
+            MsgBuf::from(Vec::with_capacity(64)),
+            MsgBuf::from(Vec::with_capacity(64)),
+            MsgBuf::from(Vec::with_capacity(8)),
+            MsgBuf::from(Vec::with_capacity(8)),
+        ],
+        vec![SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0).into(); 4],
+    );
+    for round in 0..3 {
+        for msg in msgs {
+            s1.send(msg).expect("send failed");
+        }
+        let n = (&s2).recv_msg_batch(&mut batch).expect("batch receive failed");
+        assert_eq!(n, 3, "round {round}");
+        assert!(batch.addrs[..n].iter().all(|a| a.port() == p1));
+        if round == 1 {
+            // Received into its buffer as it was, then grown so that it fits next time.
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            {
+                let full_len = Some(msgs[1].len());
+                assert!(
+                    matches!(batch.results()[1], RecvResult::Truncated { full_len: l } if l == full_len)
+                );
+                assert_eq!(batch.bufs[1].filled_part(), &msgs[1][..8]);
+                assert!(batch.bufs[1].capacity() >= msgs[1].len());
+            }
+            #[cfg(not(any(target_os = "linux", target_os = "android")))]
+            {
+                assert!(matches!(batch.results()[1], RecvResult::Spilled));
+                assert!(batch.msgs().eq(msgs.iter().copied()));
+            }
+        } else {
+            assert!(batch.msgs().eq(msgs.iter().copied()), "round {round}");
+        }
+        if round == 0 {
+            // Shifts the small buffers to the front for the next round.
+            batch.bufs.rotate_left(2);
+        }
+    }
+
+    // With a declared maximum, the buffers are grown beforehand, and only a message larger than
+    // that is reported as truncated, without losing the ones received along with it.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        let mut batch = MsgBatch::new(vec![
+            MsgBuf::from(Vec::with_capacity(4)),
+            MsgBuf::from(Vec::with_capacity(4)),
+            MsgBuf::from(Vec::with_capacity(4)),
+        ]);
+        batch.max_msg_size = Some(16);
+        let big = [0x55; 1024];
+        for msg in [msgs[0], &big, msgs[2]] {
+            s1.send(msg).expect("send failed");
+        }
+        let n = (&s2).recv_msg_batch(&mut batch).expect("batch receive failed");
+        assert_eq!(n, 3);
+        assert!(matches!(batch.results()[0], RecvResult::Fit));
+        assert!(matches!(batch.results()[1], RecvResult::Truncated { full_len: Some(1024) }));
+        assert!(matches!(batch.results()[2], RecvResult::Fit));
+        assert_eq!(batch.bufs[0].filled_part(), msgs[0]);
+        let truncated = batch.bufs[1].filled_part();
+        assert!((16..big.len()).contains(&truncated.len()));
+        assert_eq!(truncated, &big[..truncated.len()]);
+        assert_eq!(batch.bufs[2].filled_part(), msgs[2]);
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+#[allow(unsafe_code)]
+fn cmsg() {
+    use crate::os::unix::{CmsgBuf, RecvMsgWithCmsg};
+    use std::os::unix::io::AsRawFd;
+
+    let addr = Ipv4Addr::LOCALHOST;
+    let s1 = UdpSocket::bind((addr, 0)).expect("first bind failed");
+    let s2 = UdpSocket::bind((addr, 0)).expect("second bind failed");
+    s1.connect(s2.local_addr().expect("address query failed")).expect("connect failed");
+    s1.set_ttl(42).expect("TTL setup failed");
+
+    let one: libc::c_int = 1;
+    let ret = unsafe {
+        libc::setsockopt(
+            s2.as_raw_fd(),
+            libc::IPPROTO_IP,
+            libc::IP_RECVTTL,
+            &one as *const _ as *const _,
+            std::mem::size_of_val(&one) as _,
+        )
+    };
+    assert_eq!(ret, 0, "IP_RECVTTL setup failed");
+
+    s1.send(b"hi").expect("send failed");
+
+    let mut bufa = [MaybeUninit::new(0); 16];
+    let mut cbufa = [MaybeUninit::new(0); 4];
+    let mut buf = MsgBuf::from(bufa.as_mut());
+    let mut cbuf = CmsgBuf::new(MsgBuf::from(cbufa.as_mut()));
+    let rslt = (&s2).recv_msg_with_cmsg(&mut buf, &mut cbuf, None).expect("receive failed");
+    // Growing only the control message buffer doesn't count as spilling
+    assert!(matches!(rslt, RecvResult::Fit));
+    assert_eq!(buf.filled_part(), b"hi");
+    assert!(!cbuf.is_truncated());
+    let ttl = cbuf.iter().find_map(|c| c.ttl()).expect("no TTL control message");
+    assert_eq!(ttl, 42);
+
+    // Growing the message buffer does
+    let msg = b"longer than sixteen bytes";
+    s1.send(msg).expect("send failed");
+    let rslt = (&s2).recv_msg_with_cmsg(&mut buf, &mut cbuf, None).expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Spilled));
+    assert_eq!(buf.filled_part(), msg);
+    assert_eq!(cbuf.iter().find_map(|c| c.ttl()), Some(42));
+}
+
+/// Sends `msg` over `sock` with `fds` attached via `SCM_RIGHTS`.
+#[cfg(unix)]
+#[allow(unsafe_code)]
+pub(crate) fn send_with_fds(
+    sock: std::os::unix::io::BorrowedFd<'_>,
+    msg: &[u8],
+    fds: &[std::os::unix::io::BorrowedFd<'_>],
+) {
+    use std::os::unix::io::AsRawFd;
+    let raw = fds.iter().map(AsRawFd::as_raw_fd).collect::<Vec<_>>();
+    let raw_len = std::mem::size_of_val(&raw[..]);
+    let mut control = vec![0_u64; unsafe { libc::CMSG_SPACE(raw_len as _) } as usize / 8 + 1];
+    let ret = unsafe {
+        let mut iov = libc::iovec { iov_base: msg.as_ptr() as *mut _, iov_len: msg.len() };
+        let mut hdr = std::mem::zeroed::<libc::msghdr>();
+        hdr.msg_iov = &mut iov;
+        hdr.msg_iovlen = 1;
+        hdr.msg_control = control.as_mut_ptr().cast();
+        hdr.msg_controllen = libc::CMSG_SPACE(raw_len as _) as _;
+        let cmsg = libc::CMSG_FIRSTHDR(&hdr);
+        (*cmsg).cmsg_level = libc::SOL_SOCKET;
+        (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+        (*cmsg).cmsg_len = libc::CMSG_LEN(raw_len as _) as _;
+        std::ptr::copy_nonoverlapping(raw.as_ptr(), libc::CMSG_DATA(cmsg).cast(), raw.len());
+        libc::sendmsg(sock.as_raw_fd(), &hdr, 0)
+    };
+    assert_eq!(ret, msg.len() as isize, "sendmsg failed");
+}
+
+/// Asserts that `fd` refers to the same file as `orig` and has the close-on-exec flag set.
+#[cfg(unix)]
+#[allow(unsafe_code)]
+pub(crate) fn check_passed_fd(
+    fd: std::os::unix::io::BorrowedFd<'_>,
+    orig: std::os::unix::io::BorrowedFd<'_>,
+) {
+    use std::os::unix::io::AsRawFd;
+    let ino = |fd: std::os::unix::io::BorrowedFd<'_>| unsafe {
+        let mut st = std::mem::zeroed::<libc::stat>();
+        assert_eq!(libc::fstat(fd.as_raw_fd(), &mut st), 0, "fstat failed");
+        (st.st_dev, st.st_ino)
+    };
+    assert_ne!(fd.as_raw_fd(), orig.as_raw_fd());
+    assert_eq!(ino(fd), ino(orig));
+    let flags = unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_GETFD) };
+    assert_ne!(flags & libc::FD_CLOEXEC, 0, "received descriptor is not close-on-exec");
+}
+
+#[cfg(unix)]
+#[test]
+fn fds() {
+    use crate::os::unix::{CmsgBuf, RecvMsgWithFds};
+    use std::os::unix::{io::AsFd, net::UnixDatagram};
+
+    let (s1, s2) = UnixDatagram::pair().expect("socket pair creation failed");
+    let (p1, p2) = UnixDatagram::pair().expect("socket pair creation failed");
+    send_with_fds(s1.as_fd(), b"two fds", &[p1.as_fd(), p2.as_fd()]);
+    send_with_fds(s1.as_fd(), b"one fd", &[p1.as_fd()]);
+
+    let mut buf = MsgBuf::from(Vec::with_capacity(16));
+    // Too small for even one descriptor, so the descriptors received by the first peek have to be
+    // closed as the buffer grows.
+    let mut cbufa = [MaybeUninit::new(0); 4];
+    let mut cbuf = CmsgBuf::new(MsgBuf::from(cbufa.as_mut()));
+    let mut fds = Vec::new();
+    let rslt =
+        (&s2).recv_msg_with_fds(&mut buf, &mut cbuf, &mut fds, None).expect("receive failed");
+    // Only the control message buffer had to grow
+    assert!(matches!(rslt, RecvResult::Fit));
+    assert_eq!(buf.filled_part(), b"two fds");
+    assert_eq!(fds.len(), 2);
+    check_passed_fd(fds[0].as_fd(), p1.as_fd());
+    check_passed_fd(fds[1].as_fd(), p2.as_fd());
+    assert_eq!(cbuf.iter().find_map(|c| c.fd_count()), Some(2));
+
+    fds.clear();
+    let rslt =
+        (&s2).recv_msg_with_fds(&mut buf, &mut cbuf, &mut fds, None).expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Fit));
+    assert_eq!(buf.filled_part(), b"one fd");
+    assert_eq!(fds.len(), 1);
+    check_passed_fd(fds[0].as_fd(), p1.as_fd());
+}
+
+#[cfg(unix)]
+#[test]
+fn cmsg_alloc_failed() {
+    use crate::os::unix::{CmsgBuf, RecvMsgWithCmsg, RecvMsgWithFds};
+    use std::os::unix::{io::AsFd, net::UnixDatagram};
+
+    let (s1, s2) = UnixDatagram::pair().expect("socket pair creation failed");
+    let (p1, _p2) = UnixDatagram::pair().expect("socket pair creation failed");
+    send_with_fds(s1.as_fd(), b"Stays in the queue until there's memory for it", &[p1.as_fd()]);
+
+    // Neither the message nor the control message buffer getting stuck loses the message.
+    let mut buf = MsgBuf::from(());
+    let mut cbuf = CmsgBuf::default();
+    let rslt = (&s2).recv_msg_with_cmsg(&mut buf, &mut cbuf, None).expect("first receive failed");
+    assert!(matches!(rslt, RecvResult::AllocFailed(..)));
+    assert!(!buf.has_msg);
+    assert_eq!(cbuf.iter().count(), 0);
+
+    let mut buf = MsgBuf::from(Vec::new());
+    let mut cbuf = CmsgBuf::new(MsgBuf::from(()));
+    let rslt = (&s2).recv_msg_with_cmsg(&mut buf, &mut cbuf, None).expect("second receive failed");
+    assert!(matches!(rslt, RecvResult::AllocFailed(..)));
+    assert!(!buf.has_msg);
+    assert_eq!(cbuf.iter().count(), 0);
+
+    let mut cbuf = CmsgBuf::default();
+    let mut fds = Vec::new();
+    let rslt =
+        (&s2).recv_msg_with_fds(&mut buf, &mut cbuf, &mut fds, None).expect("third receive failed");
+    // The message buffer has kept what it grew to before the control message buffer failed to
+    assert!(matches!(rslt, RecvResult::Fit));
+    assert_eq!(buf.filled_part(), b"Stays in the queue until there's memory for it");
+    assert_eq!(fds.len(), 1);
+    check_passed_fd(fds[0].as_fd(), p1.as_fd());
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn credentials() {
+    use crate::os::unix::{set_pass_credentials, RecvMsgWithCredentials};
+    use std::os::unix::net::UnixDatagram;
+
+    let (s1, s2) = UnixDatagram::pair().expect("socket pair creation failed");
+    set_pass_credentials(&s2, true).expect("SO_PASSCRED setup failed");
+    s1.send(b"who am I").expect("send failed");
+
+    let mut buf = MsgBuf::from(Vec::with_capacity(16));
+    let mut creds = None;
+    let rslt = (&s2).recv_msg_with_credentials(&mut buf, &mut creds, None).expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Fit));
+    assert_eq!(buf.filled_part(), b"who am I");
+    let creds = creds.expect("no credentials received");
+    assert_eq!(creds.pid as u32, std::process::id());
+    #[allow(unsafe_code)]
+    let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+    assert_eq!((creds.uid, creds.gid), (uid, gid));
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn timestamp() {
+    use crate::os::unix::{set_timestamp_ns, set_timestamping_software, RecvMsgWithTimestamp};
+    use std::{os::unix::net::UnixDatagram, time::SystemTime};
+
+    let addr = Ipv4Addr::LOCALHOST;
+    let s1 = UdpSocket::bind((addr, 0)).expect("first bind failed");
+    let s2 = UdpSocket::bind((addr, 0)).expect("second bind failed");
+    s1.connect(s2.local_addr().expect("address query failed")).expect("connect failed");
+    set_timestamping_software(&s2, true).expect("SO_TIMESTAMPING setup failed");
+    let (u1, u2) = UnixDatagram::pair().expect("socket pair creation failed");
+    set_timestamp_ns(&u2, true).expect("SO_TIMESTAMPNS setup failed");
+
+    let before = SystemTime::now();
+    s1.send(b"tick").expect("send failed");
+    u1.send(b"tock").expect("send failed");
+
+    let mut buf = MsgBuf::from(Vec::with_capacity(16));
+    let mut ts = None;
+    let ck = |ts: Option<crate::os::unix::Timestamp>| {
+        let ts = SystemTime::from(ts.expect("no timestamp received"));
+        assert!(ts >= before);
+        assert!(ts <= SystemTime::now());
+    };
+    (&s2).recv_msg_with_timestamp(&mut buf, &mut ts, None).expect("receive failed");
+    assert_eq!(buf.filled_part(), b"tick");
+    ck(ts);
+    (&u2).recv_msg_with_timestamp(&mut buf, &mut ts, None).expect("receive failed");
+    assert_eq!(buf.filled_part(), b"tock");
+    ck(ts);
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn packet_info() {
+    use crate::os::unix::{set_recv_packet_info, RecvMsgWithPacketInfo};
+
+    for addr in [IpAddr::from(Ipv4Addr::LOCALHOST), Ipv6Addr::LOCALHOST.into()] {
+        let s1 = UdpSocket::bind((addr, 0)).expect("first bind failed");
+        let s2 = UdpSocket::bind((addr, 0)).expect("second bind failed");
+        s1.connect(s2.local_addr().expect("address query failed")).expect("connect failed");
+        set_recv_packet_info(&s2, true).expect("packet info setup failed");
+        s1.send(b"where am I").expect("send failed");
+
+        let mut buf = MsgBuf::from(Vec::with_capacity(16));
+        let mut pi = None;
+        (&s2).recv_msg_with_packet_info(&mut buf, &mut pi, None).expect("receive failed");
+        assert_eq!(buf.filled_part(), b"where am I");
+        let pi = pi.expect("no packet info received");
+        assert_eq!(pi.dst, addr);
+        assert_ne!(pi.ifindex, 0);
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn memfd() {
+    use crate::os::unix::{CmsgBuf, MemfdBuf, RecvMsgWithFds};
+    use std::{
+        fs::File,
+        os::unix::{fs::FileExt, io::AsFd, net::UnixDatagram},
+    };
+
+    let (s1, s2) = UnixDatagram::pair().expect("socket pair creation failed");
+    s1.send(&[0x55; 8192]).expect("send failed");
+    let mut buf = MsgBuf::new_owned(MemfdBuf::new());
+    let rslt = (&s2).recv_msg(&mut buf, None).expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Spilled));
+    let len = buf.len_filled();
+    assert_eq!(len, 8192);
+    let owned = buf.take_owned().expect("buffer not owned").downcast::<MemfdBuf>();
+    let owned = owned.expect("buffer not a memfd");
+
+    // Hand the memfd over to the "other process"
+    let (p1, p2) = UnixDatagram::pair().expect("socket pair creation failed");
+    send_with_fds(p1.as_fd(), b"memfd", &[owned.fd().expect("no memfd")]);
+    drop(owned);
+    let mut buf = MsgBuf::from(Vec::with_capacity(16));
+    let mut cbufa = [MaybeUninit::new(0); 64];
+    let mut cbuf = CmsgBuf::new(MsgBuf::from(cbufa.as_mut()));
+    let mut fds = Vec::new();
+    (&p2).recv_msg_with_fds(&mut buf, &mut cbuf, &mut fds, None).expect("receive failed");
+    let file = File::from(fds.pop().expect("no fd received"));
+    let mut msg = vec![0; len];
+    file.read_exact_at(&mut msg, 0).expect("read failed");
+    assert!(msg.iter().all(|&b| b == 0x55));
+}
+
+#[test]
+fn alloc_failed() {
+    let addr = Ipv4Addr::LOCALHOST;
+    let s1 = UdpSocket::bind((addr, 0)).expect("first bind failed");
+    let s2 = UdpSocket::bind((addr, 0)).expect("second bind failed");
+    s1.connect(s2.local_addr().expect("address query failed")).expect("connect failed");
+    let msg = b"Stays in the queue until there's memory for it";
+    s1.send(msg).expect("send failed");
+
+    // A buffer that is not allowed to allocate cannot receive anything.
+    let mut buf = MsgBuf::from(());
+    let rslt = (&s2).recv_msg(&mut buf, None).expect("first receive failed");
+    assert!(matches!(rslt, RecvResult::AllocFailed(..)));
+    assert!(!buf.has_msg);
+
+    let mut buf = MsgBuf::from(Vec::new());
+    let rslt = (&s2).recv_msg(&mut buf, None).expect("second receive failed");
+    assert!(matches!(rslt, RecvResult::Spilled));
+    assert_eq!(buf.filled_part(), msg);
+}
+
+#[cfg(unix)]
+#[test]
+fn oversize_pool() {
+    use crate::{msgbuf::QuotaPool, OversizePolicy};
+    use std::os::unix::net::UnixDatagram;
+
+    let (s1, s2) = UnixDatagram::pair().expect("socket pair creation failed");
+    let msg = b"This message does not fit into what the pool has to give";
+    let next = b"This one does";
+
+    // The pool rather than the quota of the buffer is what keeps it from growing at all.
+    let pool = QuotaPool::new(16);
+    let mut buf = MsgBuf::from(Vec::with_capacity(16));
+    buf.attach_to_pool(&pool).expect("attaching failed");
+    buf.oversize = OversizePolicy::Truncate;
+    s1.send(msg).expect("send failed");
+    s1.send(next).expect("send failed");
+    let rslt = (&s2).recv_msg(&mut buf, None).expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Truncated { .. }));
+    assert_eq!(buf.filled_part(), &msg[..16]);
+    let rslt = (&s2).recv_msg(&mut buf, None).expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Fit));
+    assert_eq!(buf.filled_part(), next);
+}
+
+#[test]
+fn oversize() {
+    use crate::OversizePolicy;
+
+    let addr = Ipv4Addr::LOCALHOST;
+    let s1 = UdpSocket::bind((addr, 0)).expect("first bind failed");
+    let s2 = UdpSocket::bind((addr, 0)).expect("second bind failed");
+    s1.connect(s2.local_addr().expect("address query failed")).expect("connect failed");
+    let msg = b"This message does not fit into sixteen bytes";
+    let next = b"This one does";
+    let mut buf = MsgBuf::from(Vec::with_capacity(16));
+    buf.quota = Some(16);
+
+    // Left queued: the same message keeps coming back.
+    s1.send(msg).expect("send failed");
+    for _ in 0..2 {
+        let rslt = (&s2).recv_msg(&mut buf, None).expect("receive failed");
+        assert!(matches!(rslt, RecvResult::QuotaExceeded(..)));
+    }
+
+    buf.oversize = OversizePolicy::Discard;
+    s1.send(next).expect("send failed");
+    let rslt = (&s2).recv_msg(&mut buf, None).expect("receive failed");
+    assert!(matches!(rslt, RecvResult::QuotaExceeded(..)));
+    let rslt = (&s2).recv_msg(&mut buf, None).expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Fit));
+    assert_eq!(buf.filled_part(), next);
+
+    buf.oversize = OversizePolicy::Truncate;
+    s1.send(msg).expect("send failed");
+    s1.send(next).expect("send failed");
+    let rslt = (&s2).recv_msg(&mut buf, None).expect("receive failed");
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    assert!(matches!(rslt, RecvResult::Truncated { full_len: Some(l) } if l == msg.len()));
+    assert!(matches!(rslt, RecvResult::Truncated { .. }));
+    assert_eq!(buf.filled_part(), &msg[..16]);
+    let rslt = (&s2).recv_msg(&mut buf, None).expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Fit));
+    assert_eq!(buf.filled_part(), next);
+
+    // Vectored reception goes by the policy of the last buffer.
+    #[cfg(unix)]
+    {
+        s1.send(msg).expect("send failed");
+        let mut hdr = [MaybeUninit::new(0); 8];
+        let mut bufs = [MsgBuf::from(hdr.as_mut()), buf];
+        let rslt = crate::RecvMsgVectored::recv_msg_vectored(&mut &s2, &mut bufs, None)
+            .expect("vectored receive failed");
+        assert!(matches!(rslt, RecvResult::Truncated { full_len: None }));
+        assert_eq!(bufs[0].filled_part(), &msg[..8]);
+        assert_eq!(bufs[1].filled_part(), &msg[8..24]);
+    }
+}
+
+#[test]
+fn adaptive_size() {
+    use crate::WithAdaptiveSize;
+
+    let addr = Ipv4Addr::LOCALHOST;
+    let s1 = UdpSocket::bind((addr, 0)).expect("first bind failed");
+    let s2 = UdpSocket::bind((addr, 0)).expect("second bind failed");
+    s1.connect(s2.local_addr().expect("address query failed")).expect("connect failed");
+    let mut recver = WithAdaptiveSize::new(&s2);
+
+    let mut buf = MsgBuf::from(Vec::new());
+    let big = [b'x'; 3000];
+    for i in 0..8 {
+        // The buffer is dropped every time to make it clear that it's the adapter that remembers.
+        buf = MsgBuf::from(Vec::new());
+        s1.send(&big).expect("send failed");
+        let rslt = recver.recv_msg(&mut buf, None).expect("receive failed");
+        if i == 0 {
+            assert!(matches!(rslt, RecvResult::Spilled));
+        } else {
+            assert!(matches!(rslt, RecvResult::Fit));
+        }
+        assert_eq!(buf.filled_part(), big);
+    }
+    assert_eq!(recver.estimator.estimate(), 4096);
+
+    // Enough small messages make the buffer shrink.
+    let cap = buf.capacity();
+    for _ in 0..256 {
+        s1.send(b"small").expect("send failed");
+        let rslt = recver.recv_msg(&mut buf, None).expect("receive failed");
+        assert!(matches!(rslt, RecvResult::Fit | RecvResult::Spilled));
+        assert_eq!(buf.filled_part(), b"small");
+    }
+    assert!(buf.capacity() < cap);
+}
+
+#[test]
+fn decay() {
+    use crate::msgbuf::DecayPolicy;
+
+    let addr = Ipv4Addr::LOCALHOST;
+    let s1 = UdpSocket::bind((addr, 0)).expect("first bind failed");
+    let s2 = UdpSocket::bind((addr, 0)).expect("second bind failed");
+    s1.connect(s2.local_addr().expect("address query failed")).expect("connect failed");
+
+    let mut buf = MsgBuf::from(Vec::new());
+    buf.decay = Some(DecayPolicy { after: 4, factor: 8 });
+    s1.send(&[0; 16384]).expect("send failed");
+    (&s2).recv_msg(&mut buf, None).expect("receive failed");
+    let cap = buf.capacity();
+    for _ in 0..4 {
+        s1.send(b"small").expect("send failed");
+        let rslt = (&s2).recv_msg(&mut buf, None).expect("receive failed");
+        assert!(matches!(rslt, RecvResult::Fit));
+        assert_eq!(buf.filled_part(), b"small");
+    }
+    assert!(buf.capacity() < cap);
+}
+
+#[test]
+fn return_to_borrowed() {
+    let addr = Ipv4Addr::LOCALHOST;
+    let s1 = UdpSocket::bind((addr, 0)).expect("first bind failed");
+    let s2 = UdpSocket::bind((addr, 0)).expect("second bind failed");
+    s1.connect(s2.local_addr().expect("address query failed")).expect("connect failed");
+
+    let mut slice = [MaybeUninit::new(0); 64];
+    let ptr = slice.as_ptr().cast::<u8>();
+    let mut buf = MsgBuf::from(slice.as_mut());
+    buf.return_to_borrowed = true;
+    s1.send(&[0; 1024]).expect("send failed");
+    let rslt = (&s2).recv_msg(&mut buf, None).expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Spilled));
+    assert_ne!(buf.as_ptr(), ptr);
+    s1.send(b"small").expect("send failed");
+    let rslt = (&s2).recv_msg(&mut buf, None).expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Fit));
+    assert_eq!(buf.as_ptr(), ptr);
+    assert_eq!(buf.filled_part(), b"small");
+}
+
+#[cfg(unix)]
+#[test]
+#[allow(unsafe_code)]
+fn empty_msg_vs_eof() {
+    use std::os::unix::{
+        io::{FromRawFd, OwnedFd},
+        net::UnixDatagram,
+    };
+
+    let addr = Ipv4Addr::LOCALHOST;
+    let s1 = UdpSocket::bind((addr, 0)).expect("first bind failed");
+    let s2 = UdpSocket::bind((addr, 0)).expect("second bind failed");
+    s1.connect(s2.local_addr().expect("address query failed")).expect("connect failed");
+    s1.send(b"").expect("send failed");
+    s1.send(b"after").expect("send failed");
+
+    let mut buf = MsgBuf::from(Vec::with_capacity(16));
+    let rslt = (&s2).recv_msg(&mut buf, None).expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Fit));
+    assert!(buf.has_msg);
+    assert_eq!(buf.filled_part(), b"");
+    let rslt = (&s2).recv_msg(&mut buf, None).expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Fit));
+    assert_eq!(buf.filled_part(), b"after");
+
+    // The datagram impls work on SOCK_SEQPACKET sockets just the same, save for EOF.
+    let mut fds = [0; 2];
+    let ret = unsafe { libc::socketpair(libc::AF_UNIX, libc::SOCK_SEQPACKET, 0, fds.as_mut_ptr()) };
+    assert_eq!(ret, 0, "socket pair creation failed");
+    let (q1, q2) = unsafe {
+        (
+            UnixDatagram::from(OwnedFd::from_raw_fd(fds[0])),
+            UnixDatagram::from(OwnedFd::from_raw_fd(fds[1])),
+        )
+    };
+    q1.send(b"last").expect("send failed");
+    drop(q1);
+    let rslt = (&q2).recv_msg(&mut buf, None).expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Fit));
+    assert_eq!(buf.filled_part(), b"last");
+    let rslt = (&q2).recv_msg(&mut buf, None).expect("receive failed");
+    assert!(matches!(rslt, RecvResult::EndOfStream));
+}
+
+#[cfg(unix)]
+#[test]
+fn seqpacket() {
+    use crate::os::unix::{UnixSeqpacket, UnixSeqpacketListener};
+    use std::net::Shutdown;
+
+    let path = std::env::temp_dir().join(format!("recvmsg-seqpacket-{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let listener = UnixSeqpacketListener::bind(&path).expect("bind failed");
+    let client = UnixSeqpacket::connect(&path).expect("connect failed");
+    let (server, _) = listener.accept().expect("accept failed");
+    let _ = std::fs::remove_file(&path);
+
+    let msg = b"a message that does not fit into the initial buffer";
+    client.send(msg).expect("send failed");
+    client.send(b"short").expect("send failed");
+    client.shutdown(Shutdown::Write).expect("shutdown failed");
+
+    let mut bufa = [MaybeUninit::new(0); 8];
+    let mut buf = MsgBuf::from(bufa.as_mut());
+    let rslt = (&server).recv_msg(&mut buf, None).expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Spilled));
+    assert_eq!(buf.filled_part(), msg);
+    let rslt = (&server).recv_msg(&mut buf, None).expect("receive failed");
+    assert!(matches!(rslt, RecvResult::Fit));
+    assert_eq!(buf.filled_part(), b"short");
+    let rslt = (&server).recv_msg(&mut buf, None).expect("receive failed");
+    assert!(matches!(rslt, RecvResult::EndOfStream));
+}
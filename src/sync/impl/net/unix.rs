@@ -1,12 +1,18 @@
 #![allow(unsafe_code)]
 
+pub(crate) mod batch;
 mod extract_address;
 mod r#impl;
 pub(crate) mod wrap;
 
-use crate::{MsgBuf, RecvMsg, RecvResult, TruncatingRecvMsg};
+use crate::{
+    os::unix::{CmsgBuf, RecvMsgWithCmsg, TruncatingRecvMsgWithCmsg, UnixSeqpacket},
+    MsgBatch, MsgBuf, RecvMsg, RecvMsgBatch, RecvMsgVectored, RecvResult, TruncatingRecvMsg,
+    TruncatingRecvMsgVectored,
+};
 #[cfg(any(target_os = "linux", target_os = "android"))]
 use crate::{TruncatingRecvMsgWithFullSize, TryRecvResult};
+use extract_address::{extract_ip_address, extract_unix_address};
 use std::{
     io,
     net::{SocketAddr as InetAddr, UdpSocket},
@@ -44,6 +50,30 @@ impl TruncatingRecvMsg for UdpSocket {
     }
 }
 
+impl TruncatingRecvMsgVectored for &UdpSocket {
+    #[inline]
+    fn recv_trunc_vectored(
+        &mut self,
+        peek: bool,
+        bufs: &mut [MsgBuf<'_>],
+        abuf: Option<&mut InetAddr>,
+    ) -> io::Result<Option<bool>> {
+        wrap::recv_trunc_vectored_ip(self.as_fd(), peek, bufs, abuf)
+    }
+}
+
+impl TruncatingRecvMsgVectored for UdpSocket {
+    #[inline]
+    fn recv_trunc_vectored(
+        &mut self,
+        peek: bool,
+        bufs: &mut [MsgBuf<'_>],
+        abuf: Option<&mut InetAddr>,
+    ) -> io::Result<Option<bool>> {
+        (&*self).recv_trunc_vectored(peek, bufs, abuf)
+    }
+}
+
 /// Linux-only, requires kernel 3.4 or newer.
 #[cfg(any(target_os = "linux", target_os = "android"))]
 impl TruncatingRecvMsgWithFullSize for &UdpSocket {
@@ -98,6 +128,92 @@ impl RecvMsg for UdpSocket {
     }
 }
 
+impl RecvMsgVectored for &UdpSocket {
+    #[inline]
+    fn recv_msg_vectored(
+        &mut self,
+        bufs: &mut [MsgBuf<'_>],
+        abuf: Option<&mut InetAddr>,
+    ) -> io::Result<RecvResult> {
+        wrap::recv_msg_vectored_ip(self.as_fd(), bufs, abuf)
+    }
+}
+
+impl RecvMsgVectored for UdpSocket {
+    #[inline]
+    fn recv_msg_vectored(
+        &mut self,
+        bufs: &mut [MsgBuf<'_>],
+        abuf: Option<&mut InetAddr>,
+    ) -> io::Result<RecvResult> {
+        (&mut &*self).recv_msg_vectored(bufs, abuf)
+    }
+}
+
+impl RecvMsgBatch for &UdpSocket {
+    #[inline]
+    fn recv_msg_batch(&mut self, batch: &mut MsgBatch<'_, Self::AddrBuf>) -> io::Result<usize> {
+        batch::recv_msg_batch(self.as_fd(), batch, extract_ip_address)
+    }
+}
+
+impl RecvMsgBatch for UdpSocket {
+    #[inline]
+    fn recv_msg_batch(&mut self, batch: &mut MsgBatch<'_, Self::AddrBuf>) -> io::Result<usize> {
+        (&mut &*self).recv_msg_batch(batch)
+    }
+}
+
+impl TruncatingRecvMsgWithCmsg for &UdpSocket {
+    #[inline]
+    fn recv_trunc_with_cmsg(
+        &mut self,
+        peek: bool,
+        buf: &mut MsgBuf<'_>,
+        cbuf: &mut CmsgBuf<'_>,
+        abuf: Option<&mut InetAddr>,
+    ) -> io::Result<Option<bool>> {
+        wrap::recv_trunc_with_cmsg_ip(self.as_fd(), peek, buf, cbuf, abuf)
+    }
+}
+
+impl TruncatingRecvMsgWithCmsg for UdpSocket {
+    #[inline]
+    fn recv_trunc_with_cmsg(
+        &mut self,
+        peek: bool,
+        buf: &mut MsgBuf<'_>,
+        cbuf: &mut CmsgBuf<'_>,
+        abuf: Option<&mut InetAddr>,
+    ) -> io::Result<Option<bool>> {
+        (&*self).recv_trunc_with_cmsg(peek, buf, cbuf, abuf)
+    }
+}
+
+impl RecvMsgWithCmsg for &UdpSocket {
+    #[inline]
+    fn recv_msg_with_cmsg(
+        &mut self,
+        buf: &mut MsgBuf<'_>,
+        cbuf: &mut CmsgBuf<'_>,
+        abuf: Option<&mut InetAddr>,
+    ) -> io::Result<RecvResult> {
+        wrap::recv_msg_with_cmsg_ip(self.as_fd(), buf, cbuf, abuf)
+    }
+}
+
+impl RecvMsgWithCmsg for UdpSocket {
+    #[inline]
+    fn recv_msg_with_cmsg(
+        &mut self,
+        buf: &mut MsgBuf<'_>,
+        cbuf: &mut CmsgBuf<'_>,
+        abuf: Option<&mut InetAddr>,
+    ) -> io::Result<RecvResult> {
+        (&mut &*self).recv_msg_with_cmsg(buf, cbuf, abuf)
+    }
+}
+
 impl TruncatingRecvMsg for &UnixDatagram {
     type Error = io::Error;
     type AddrBuf = UnixAddr;
@@ -126,6 +242,30 @@ impl TruncatingRecvMsg for UnixDatagram {
     }
 }
 
+impl TruncatingRecvMsgVectored for &UnixDatagram {
+    #[inline]
+    fn recv_trunc_vectored(
+        &mut self,
+        peek: bool,
+        bufs: &mut [MsgBuf<'_>],
+        abuf: Option<&mut UnixAddr>,
+    ) -> io::Result<Option<bool>> {
+        wrap::recv_trunc_vectored_unix(self.as_fd(), peek, bufs, abuf)
+    }
+}
+
+impl TruncatingRecvMsgVectored for UnixDatagram {
+    #[inline]
+    fn recv_trunc_vectored(
+        &mut self,
+        peek: bool,
+        bufs: &mut [MsgBuf<'_>],
+        abuf: Option<&mut UnixAddr>,
+    ) -> io::Result<Option<bool>> {
+        (&*self).recv_trunc_vectored(peek, bufs, abuf)
+    }
+}
+
 /// Linux-only, requires kernel 3.4 or newer.
 #[cfg(any(target_os = "linux", target_os = "android"))]
 impl TruncatingRecvMsgWithFullSize for &UnixDatagram {
@@ -179,3 +319,267 @@ impl RecvMsg for UnixDatagram {
         (&mut &*self).recv_msg(buf, abuf)
     }
 }
+
+impl RecvMsgVectored for &UnixDatagram {
+    #[inline]
+    fn recv_msg_vectored(
+        &mut self,
+        bufs: &mut [MsgBuf<'_>],
+        abuf: Option<&mut UnixAddr>,
+    ) -> io::Result<RecvResult> {
+        wrap::recv_msg_vectored_unix(self.as_fd(), bufs, abuf)
+    }
+}
+
+impl RecvMsgVectored for UnixDatagram {
+    #[inline]
+    fn recv_msg_vectored(
+        &mut self,
+        bufs: &mut [MsgBuf<'_>],
+        abuf: Option<&mut UnixAddr>,
+    ) -> io::Result<RecvResult> {
+        (&mut &*self).recv_msg_vectored(bufs, abuf)
+    }
+}
+
+impl RecvMsgBatch for &UnixDatagram {
+    #[inline]
+    fn recv_msg_batch(&mut self, batch: &mut MsgBatch<'_, Self::AddrBuf>) -> io::Result<usize> {
+        batch::recv_msg_batch(self.as_fd(), batch, extract_unix_address)
+    }
+}
+
+impl RecvMsgBatch for UnixDatagram {
+    #[inline]
+    fn recv_msg_batch(&mut self, batch: &mut MsgBatch<'_, Self::AddrBuf>) -> io::Result<usize> {
+        (&mut &*self).recv_msg_batch(batch)
+    }
+}
+
+impl TruncatingRecvMsgWithCmsg for &UnixDatagram {
+    #[inline]
+    fn recv_trunc_with_cmsg(
+        &mut self,
+        peek: bool,
+        buf: &mut MsgBuf<'_>,
+        cbuf: &mut CmsgBuf<'_>,
+        abuf: Option<&mut UnixAddr>,
+    ) -> io::Result<Option<bool>> {
+        wrap::recv_trunc_with_cmsg_unix(self.as_fd(), peek, buf, cbuf, abuf)
+    }
+}
+
+impl TruncatingRecvMsgWithCmsg for UnixDatagram {
+    #[inline]
+    fn recv_trunc_with_cmsg(
+        &mut self,
+        peek: bool,
+        buf: &mut MsgBuf<'_>,
+        cbuf: &mut CmsgBuf<'_>,
+        abuf: Option<&mut UnixAddr>,
+    ) -> io::Result<Option<bool>> {
+        (&*self).recv_trunc_with_cmsg(peek, buf, cbuf, abuf)
+    }
+}
+
+impl RecvMsgWithCmsg for &UnixDatagram {
+    #[inline]
+    fn recv_msg_with_cmsg(
+        &mut self,
+        buf: &mut MsgBuf<'_>,
+        cbuf: &mut CmsgBuf<'_>,
+        abuf: Option<&mut UnixAddr>,
+    ) -> io::Result<RecvResult> {
+        wrap::recv_msg_with_cmsg_unix(self.as_fd(), buf, cbuf, abuf)
+    }
+}
+
+impl RecvMsgWithCmsg for UnixDatagram {
+    #[inline]
+    fn recv_msg_with_cmsg(
+        &mut self,
+        buf: &mut MsgBuf<'_>,
+        cbuf: &mut CmsgBuf<'_>,
+        abuf: Option<&mut UnixAddr>,
+    ) -> io::Result<RecvResult> {
+        (&mut &*self).recv_msg_with_cmsg(buf, cbuf, abuf)
+    }
+}
+
+impl TruncatingRecvMsg for &UnixSeqpacket {
+    type Error = io::Error;
+    type AddrBuf = UnixAddr;
+    #[inline]
+    fn recv_trunc(
+        &mut self,
+        peek: bool,
+        buf: &mut MsgBuf<'_>,
+        abuf: Option<&mut UnixAddr>,
+    ) -> io::Result<Option<bool>> {
+        wrap::recv_trunc_unix(self.as_fd(), peek, buf, abuf)
+    }
+}
+
+impl TruncatingRecvMsg for UnixSeqpacket {
+    type Error = io::Error;
+    type AddrBuf = UnixAddr;
+    #[inline]
+    fn recv_trunc(
+        &mut self,
+        peek: bool,
+        buf: &mut MsgBuf<'_>,
+        abuf: Option<&mut UnixAddr>,
+    ) -> io::Result<Option<bool>> {
+        (&*self).recv_trunc(peek, buf, abuf)
+    }
+}
+
+impl TruncatingRecvMsgVectored for &UnixSeqpacket {
+    #[inline]
+    fn recv_trunc_vectored(
+        &mut self,
+        peek: bool,
+        bufs: &mut [MsgBuf<'_>],
+        abuf: Option<&mut UnixAddr>,
+    ) -> io::Result<Option<bool>> {
+        wrap::recv_trunc_vectored_unix(self.as_fd(), peek, bufs, abuf)
+    }
+}
+
+impl TruncatingRecvMsgVectored for UnixSeqpacket {
+    #[inline]
+    fn recv_trunc_vectored(
+        &mut self,
+        peek: bool,
+        bufs: &mut [MsgBuf<'_>],
+        abuf: Option<&mut UnixAddr>,
+    ) -> io::Result<Option<bool>> {
+        (&*self).recv_trunc_vectored(peek, bufs, abuf)
+    }
+}
+
+/// Linux-only, requires kernel 3.4 or newer.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl TruncatingRecvMsgWithFullSize for &UnixSeqpacket {
+    #[inline]
+    fn recv_trunc_with_full_size(
+        &mut self,
+        peek: bool,
+        buf: &mut MsgBuf<'_>,
+        abuf: Option<&mut UnixAddr>,
+    ) -> io::Result<TryRecvResult> {
+        wrap::recv_trunc_with_full_size_unix(self.as_fd(), peek, buf, abuf)
+    }
+}
+
+/// Linux-only, requires kernel 3.4 or newer.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl TruncatingRecvMsgWithFullSize for UnixSeqpacket {
+    #[inline]
+    fn recv_trunc_with_full_size(
+        &mut self,
+        peek: bool,
+        buf: &mut MsgBuf<'_>,
+        abuf: Option<&mut UnixAddr>,
+    ) -> io::Result<TryRecvResult> {
+        (&*self).recv_trunc_with_full_size(peek, buf, abuf)
+    }
+}
+
+impl RecvMsg for &UnixSeqpacket {
+    type Error = io::Error;
+    type AddrBuf = UnixAddr;
+    #[inline]
+    fn recv_msg(
+        &mut self,
+        buf: &mut MsgBuf<'_>,
+        abuf: Option<&mut UnixAddr>,
+    ) -> io::Result<RecvResult> {
+        wrap::recv_msg_unix(self.as_fd(), buf, abuf)
+    }
+}
+
+impl RecvMsg for UnixSeqpacket {
+    type Error = io::Error;
+    type AddrBuf = UnixAddr;
+    #[inline]
+    fn recv_msg(
+        &mut self,
+        buf: &mut MsgBuf<'_>,
+        abuf: Option<&mut UnixAddr>,
+    ) -> io::Result<RecvResult> {
+        (&mut &*self).recv_msg(buf, abuf)
+    }
+}
+
+impl RecvMsgVectored for &UnixSeqpacket {
+    #[inline]
+    fn recv_msg_vectored(
+        &mut self,
+        bufs: &mut [MsgBuf<'_>],
+        abuf: Option<&mut UnixAddr>,
+    ) -> io::Result<RecvResult> {
+        wrap::recv_msg_vectored_unix(self.as_fd(), bufs, abuf)
+    }
+}
+
+impl RecvMsgVectored for UnixSeqpacket {
+    #[inline]
+    fn recv_msg_vectored(
+        &mut self,
+        bufs: &mut [MsgBuf<'_>],
+        abuf: Option<&mut UnixAddr>,
+    ) -> io::Result<RecvResult> {
+        (&mut &*self).recv_msg_vectored(bufs, abuf)
+    }
+}
+
+impl TruncatingRecvMsgWithCmsg for &UnixSeqpacket {
+    #[inline]
+    fn recv_trunc_with_cmsg(
+        &mut self,
+        peek: bool,
+        buf: &mut MsgBuf<'_>,
+        cbuf: &mut CmsgBuf<'_>,
+        abuf: Option<&mut UnixAddr>,
+    ) -> io::Result<Option<bool>> {
+        wrap::recv_trunc_with_cmsg_unix(self.as_fd(), peek, buf, cbuf, abuf)
+    }
+}
+
+impl TruncatingRecvMsgWithCmsg for UnixSeqpacket {
+    #[inline]
+    fn recv_trunc_with_cmsg(
+        &mut self,
+        peek: bool,
+        buf: &mut MsgBuf<'_>,
+        cbuf: &mut CmsgBuf<'_>,
+        abuf: Option<&mut UnixAddr>,
+    ) -> io::Result<Option<bool>> {
+        (&*self).recv_trunc_with_cmsg(peek, buf, cbuf, abuf)
+    }
+}
+
+impl RecvMsgWithCmsg for &UnixSeqpacket {
+    #[inline]
+    fn recv_msg_with_cmsg(
+        &mut self,
+        buf: &mut MsgBuf<'_>,
+        cbuf: &mut CmsgBuf<'_>,
+        abuf: Option<&mut UnixAddr>,
+    ) -> io::Result<RecvResult> {
+        wrap::recv_msg_with_cmsg_unix(self.as_fd(), buf, cbuf, abuf)
+    }
+}
+
+impl RecvMsgWithCmsg for UnixSeqpacket {
+    #[inline]
+    fn recv_msg_with_cmsg(
+        &mut self,
+        buf: &mut MsgBuf<'_>,
+        cbuf: &mut CmsgBuf<'_>,
+        abuf: Option<&mut UnixAddr>,
+    ) -> io::Result<RecvResult> {
+        (&mut &*self).recv_msg_with_cmsg(buf, cbuf, abuf)
+    }
+}
@@ -0,0 +1,215 @@
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+use super::{r#impl::recv_msg_with_flags, wrap::prepare_storage};
+use crate::MsgBatch;
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+use crate::RecvResult;
+use libc::{sockaddr_storage, socklen_t, MSG_DONTWAIT};
+use std::{io, os::fd::BorrowedFd};
+
+type Extract<AB> = fn(&sockaddr_storage, socklen_t) -> io::Result<AB>;
+
+pub(crate) fn recv_msg_batch<AB>(
+    fd: BorrowedFd<'_>,
+    batch: &mut MsgBatch<'_, AB>,
+    extract: Extract<AB>,
+) -> io::Result<usize> {
+    let MsgBatch { bufs, addrs, max_msg_size, results, deferred_err } = batch;
+    results.clear();
+    if let Some(e) = deferred_err.take() {
+        return Err(e);
+    }
+    let want_addrs = !addrs.is_empty();
+    assert!(!want_addrs || addrs.len() >= bufs.len(), "fewer address buffers than message buffers");
+
+    // Errors that occur after the first message has been received are returned by the next call.
+    let mut defer = |e: io::Error, received: bool| {
+        if !received {
+            return Err(e);
+        }
+        if e.kind() != io::ErrorKind::WouldBlock {
+            deferred_err.get_or_insert(e);
+        }
+        Ok(())
+    };
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        // Buffers that can't be grown to the declared maximum aren't used for this batch.
+        let mut usable = bufs.len();
+        if let Some(max_msg_size) = *max_msg_size {
+            for (i, buf) in bufs.iter_mut().enumerate() {
+                if let Err(e) = buf.try_grow_to(max_msg_size) {
+                    if i == 0 {
+                        results.push(e.into());
+                    }
+                    usable = i;
+                    break;
+                }
+            }
+        }
+        while results.len() < usable {
+            let i = results.len();
+            // Only the first message is waited for.
+            let flags = if i == 0 { 0 } else { MSG_DONTWAIT };
+            let addrs = if want_addrs { Some(&mut addrs[i..usable]) } else { None };
+            match mmsg::recv_run(fd, &mut bufs[i..usable], addrs, extract, flags, results) {
+                Ok((n, extract_err)) => {
+                    if let Some(e) = extract_err {
+                        defer(e, true)?;
+                    }
+                    if n < (usable - i).min(mmsg::MAX_RUN) {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    defer(e, i > 0)?;
+                    break;
+                }
+            }
+        }
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    {
+        let _ = max_msg_size;
+        while results.len() < bufs.len() {
+            let i = results.len();
+            // Only the first message is waited for.
+            let flags = if i == 0 { 0 } else { MSG_DONTWAIT };
+            let mut fused_abuf = prepare_storage();
+            let rslt = match recv_msg_with_flags(
+                fd,
+                &mut bufs[i],
+                want_addrs.then_some(&mut fused_abuf),
+                flags,
+            ) {
+                Ok(rslt) => rslt,
+                Err(e) => {
+                    defer(e, i > 0)?;
+                    break;
+                }
+            };
+            results.push(rslt);
+            match rslt {
+                RecvResult::Fit | RecvResult::Spilled | RecvResult::Truncated { .. } => {
+                    if want_addrs {
+                        let (storage, len) = &fused_abuf;
+                        match extract(storage, *len) {
+                            Ok(addr) => addrs[i] = addr,
+                            Err(e) => defer(e, true)?,
+                        }
+                    }
+                }
+                RecvResult::EndOfStream
+                | RecvResult::QuotaExceeded(..)
+                | RecvResult::AllocFailed(..) => break,
+            }
+        }
+    }
+    Ok(results.len())
+}
+
+/// `recvmmsg()`-based reception of several messages into buffers as large as they currently are.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod mmsg {
+    use super::Extract;
+    use crate::{MsgBuf, RecvResult};
+    use libc::{
+        iovec, mmsghdr, recvmmsg, sockaddr_storage, MSG_DONTWAIT, MSG_TRUNC, MSG_WAITFORONE,
+    };
+    use std::{
+        cmp::min,
+        io,
+        mem::{size_of, zeroed},
+        os::fd::{AsRawFd, BorrowedFd},
+        ptr,
+    };
+
+    /// The most messages received by one `recvmmsg()` call, which bounds the scratch space kept on
+    /// the stack.
+    pub(super) const MAX_RUN: usize = 16;
+
+    /// Receives up to `bufs.len()` messages (or [`MAX_RUN`], whichever is smaller), pushing a
+    /// result for each of them and returning how many were received.
+    ///
+    /// A message that turns out to be larger than its buffer is received truncated, with its full
+    /// length reported via `RecvResult::Truncated`, since it has already been taken off the queue by
+    /// then. The buffer is then grown to that length, quota permitting, so that a message of the
+    /// same size fits into it next time. Failure to convert a sender address doesn't keep the
+    /// message from being reported; the first such error is returned alongside the count, and the
+    /// address buffer is left unchanged.
+    ///
+    /// Only used with datagram sockets, on which a message of length zero is just an empty message
+    /// rather than the end of the stream.
+    pub(super) fn recv_run<AB>(
+        fd: BorrowedFd<'_>,
+        bufs: &mut [MsgBuf<'_>],
+        mut addrs: Option<&mut [AB]>,
+        extract: Extract<AB>,
+        flags: i32,
+        results: &mut Vec<RecvResult>,
+    ) -> io::Result<(usize, Option<io::Error>)> {
+        let run = min(bufs.len(), MAX_RUN);
+        let bufs = &mut bufs[..run];
+        let mut iovs: [iovec; MAX_RUN] = unsafe { zeroed() };
+        let mut storage: [sockaddr_storage; MAX_RUN] = unsafe { zeroed() };
+        let mut hdrs: [mmsghdr; MAX_RUN] = unsafe { zeroed() };
+        for (((buf, iov), storage), hdr) in
+            bufs.iter_mut().zip(&mut iovs).zip(&mut storage).zip(&mut hdrs)
+        {
+            buf.set_fill(0);
+            buf.has_msg = false;
+            let out = &mut buf[..];
+            *iov = iovec { iov_base: out.as_mut_ptr().cast(), iov_len: out.len() };
+            hdr.msg_hdr.msg_iov = iov;
+            hdr.msg_hdr.msg_iovlen = 1;
+            if addrs.is_some() {
+                hdr.msg_hdr.msg_name = (storage as *mut sockaddr_storage).cast();
+                hdr.msg_hdr.msg_namelen = size_of::<sockaddr_storage>() as _;
+            }
+        }
+
+        // MSG_TRUNC makes msg_len the full length of the message even if it was truncated
+        let flags = flags | MSG_TRUNC | if flags & MSG_DONTWAIT == 0 { MSG_WAITFORONE } else { 0 };
+        let nmsgs = unsafe {
+            // SAFETY: the first bufs.len() mmsghdrs are zero-initialized except for pointers to
+            // live iovecs and address buffers
+            recvmmsg(
+                fd.as_raw_fd(),
+                hdrs.as_mut_ptr(),
+                bufs.len() as _,
+                flags as _,
+                ptr::null_mut(),
+            )
+        };
+        let nmsgs = if nmsgs < 0 {
+            return Err(io::Error::last_os_error());
+        } else {
+            nmsgs as usize
+        };
+
+        let mut extract_err = None;
+        for (i, hdr) in hdrs[..nmsgs].iter().enumerate() {
+            let buf = &mut bufs[i];
+            let full_len = hdr.msg_len as usize;
+            let len = min(full_len, buf.capacity());
+            unsafe { buf.advance_init_and_set_fill(len) };
+            buf.has_msg = true;
+            if hdr.msg_hdr.msg_flags & MSG_TRUNC != 0 {
+                results.push(buf.after_recv(RecvResult::Truncated { full_len: Some(full_len) }));
+                let _ = buf.try_grow_to(full_len);
+            } else {
+                results.push(buf.after_recv(RecvResult::Fit));
+            }
+            if let Some(addrs) = addrs.as_deref_mut() {
+                match extract(&storage[i], hdr.msg_hdr.msg_namelen) {
+                    Ok(addr) => addrs[i] = addr,
+                    Err(e) => {
+                        extract_err.get_or_insert(e);
+                    }
+                }
+            }
+        }
+        Ok((nmsgs, extract_err))
+    }
+}
@@ -1,25 +1,103 @@
 #[cfg(any(target_os = "linux", target_os = "android"))]
 use crate::TryRecvResult;
-use crate::{os::unix::recv_trunc_recvmsg_with_msghdr, MsgBuf, RecvResult, TruncatingRecvMsg};
-use libc::{msghdr, sockaddr_storage, socklen_t, MSG_PEEK};
-use std::{io, mem::zeroed, os::fd::BorrowedFd};
+use crate::{
+    os::unix::{
+        recv_trunc_recvmsg_vectored_with_msghdr, recv_trunc_recvmsg_with_msghdr, CmsgBuf,
+        TruncatingRecvMsgWithCmsg,
+    },
+    MsgBuf, RecvResult, TruncatingRecvMsg, TruncatingRecvMsgVectored,
+};
+use libc::{c_int, msghdr, sockaddr_storage, socklen_t, MSG_CTRUNC, MSG_PEEK};
+use std::{io, mem::zeroed, os::fd::BorrowedFd, slice};
 
 pub(crate) fn recv_trunc(
     fd: BorrowedFd<'_>,
     peek: bool,
     buf: &mut MsgBuf<'_>,
+    abuf: Option<&mut (sockaddr_storage, socklen_t)>,
+) -> io::Result<Option<bool>> {
+    recv_trunc_vectored(fd, peek, slice::from_mut(buf), abuf)
+}
+
+pub(crate) fn recv_trunc_vectored(
+    fd: BorrowedFd<'_>,
+    peek: bool,
+    bufs: &mut [MsgBuf<'_>],
+    abuf: Option<&mut (sockaddr_storage, socklen_t)>,
+) -> io::Result<Option<bool>> {
+    recv_trunc_vectored_with_flags(fd, peek, bufs, abuf, 0)
+}
+
+fn recv_trunc_vectored_with_flags(
+    fd: BorrowedFd<'_>,
+    peek: bool,
+    bufs: &mut [MsgBuf<'_>],
     mut abuf: Option<&mut (sockaddr_storage, socklen_t)>,
+    flags: c_int,
 ) -> io::Result<Option<bool>> {
     unsafe {
         let mut hdr = zeroed::<msghdr>();
+        let ret = recv_trunc_recvmsg_vectored_with_msghdr(
+            fd,
+            &mut hdr,
+            bufs,
+            abuf.as_deref_mut().map(|(s, _)| s),
+            flags | if peek { MSG_PEEK } else { 0 },
+        )?
+        .0;
+        if let Some((_, ref mut nl)) = abuf {
+            *nl = hdr.msg_namelen;
+        }
+        Ok(ret)
+    }
+}
+
+/// Flags passed to every `recvmsg()` call that receives control messages, so that file descriptors
+/// received via `SCM_RIGHTS` don't leak into child processes.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+const CMSG_FLAGS: c_int = libc::MSG_CMSG_CLOEXEC;
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+)))]
+const CMSG_FLAGS: c_int = 0;
+
+pub(crate) fn recv_trunc_with_cmsg(
+    fd: BorrowedFd<'_>,
+    peek: bool,
+    buf: &mut MsgBuf<'_>,
+    cbuf: &mut CmsgBuf<'_>,
+    mut abuf: Option<&mut (sockaddr_storage, socklen_t)>,
+    flags: c_int,
+) -> io::Result<Option<bool>> {
+    unsafe {
+        let mut hdr = zeroed::<msghdr>();
+        let (control, controllen) = cbuf.prepare();
+        hdr.msg_control = control;
+        hdr.msg_controllen = controllen as _;
         let ret = recv_trunc_recvmsg_with_msghdr(
             fd,
             &mut hdr,
             buf,
             abuf.as_deref_mut().map(|(s, _)| s),
-            if peek { MSG_PEEK } else { 0 },
+            flags | CMSG_FLAGS | if peek { MSG_PEEK } else { 0 },
         )?
         .0;
+        if ret.is_some() {
+            // SAFETY: the system has initialized that much for us
+            cbuf.set_received(hdr.msg_controllen as _, hdr.msg_flags & MSG_CTRUNC != 0);
+        }
         if let Some((_, ref mut nl)) = abuf {
             *nl = hdr.msg_namelen;
         }
@@ -29,10 +107,21 @@ pub(crate) fn recv_trunc(
 
 #[cfg(any(target_os = "linux", target_os = "android"))]
 pub(crate) fn recv_trunc_with_full_size(
+    fd: BorrowedFd<'_>,
+    peek: bool,
+    buf: &mut MsgBuf<'_>,
+    abuf: Option<&mut (sockaddr_storage, socklen_t)>,
+) -> io::Result<TryRecvResult> {
+    recv_trunc_with_full_size_with_flags(fd, peek, buf, abuf, 0)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn recv_trunc_with_full_size_with_flags(
     fd: BorrowedFd<'_>,
     peek: bool,
     buf: &mut MsgBuf<'_>,
     mut abuf: Option<&mut (sockaddr_storage, socklen_t)>,
+    flags: c_int,
 ) -> io::Result<TryRecvResult> {
     Ok(
         match unsafe {
@@ -42,57 +131,125 @@ pub(crate) fn recv_trunc_with_full_size(
                 &mut hdr,
                 buf,
                 abuf.as_deref_mut().map(|(s, _)| s),
-                libc::MSG_TRUNC | if peek { MSG_PEEK } else { 0 },
+                flags | libc::MSG_TRUNC | if peek { MSG_PEEK } else { 0 },
             )?;
             if let Some((_, ref mut nl)) = abuf {
                 *nl = hdr.msg_namelen;
             }
             rtr
         } {
-            (Some(true), sz) => TryRecvResult::Fit(sz),
+            (Some(true), _) => TryRecvResult::Fit,
             (Some(false), sz) => TryRecvResult::Spilled(sz),
             (None, ..) => TryRecvResult::EndOfStream,
         },
     )
 }
 
+/// The file descriptor and the flags to pass to every `recvmsg()` call in addition to the ones
+/// required by the operation.
+struct Impl<'a>(BorrowedFd<'a>, c_int);
+impl TruncatingRecvMsg for Impl<'_> {
+    type Error = io::Error;
+    type AddrBuf = (sockaddr_storage, socklen_t);
+    fn recv_trunc(
+        &mut self,
+        peek: bool,
+        buf: &mut MsgBuf<'_>,
+        abuf: Option<&mut (sockaddr_storage, socklen_t)>,
+    ) -> Result<Option<bool>, Self::Error> {
+        recv_trunc_vectored_with_flags(self.0, peek, slice::from_mut(buf), abuf, self.1)
+    }
+}
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl crate::TruncatingRecvMsgWithFullSize for Impl<'_> {
+    fn recv_trunc_with_full_size(
+        &mut self,
+        peek: bool,
+        buf: &mut MsgBuf<'_>,
+        abuf: Option<&mut (sockaddr_storage, socklen_t)>,
+    ) -> Result<TryRecvResult, Self::Error> {
+        recv_trunc_with_full_size_with_flags(self.0, peek, buf, abuf, self.1)
+    }
+}
+impl TruncatingRecvMsgVectored for Impl<'_> {
+    fn recv_trunc_vectored(
+        &mut self,
+        peek: bool,
+        bufs: &mut [MsgBuf<'_>],
+        abuf: Option<&mut (sockaddr_storage, socklen_t)>,
+    ) -> Result<Option<bool>, Self::Error> {
+        recv_trunc_vectored_with_flags(self.0, peek, bufs, abuf, self.1)
+    }
+}
+impl TruncatingRecvMsgWithCmsg for Impl<'_> {
+    fn recv_trunc_with_cmsg(
+        &mut self,
+        peek: bool,
+        buf: &mut MsgBuf<'_>,
+        cbuf: &mut CmsgBuf<'_>,
+        abuf: Option<&mut (sockaddr_storage, socklen_t)>,
+    ) -> Result<Option<bool>, Self::Error> {
+        recv_trunc_with_cmsg(self.0, peek, buf, cbuf, abuf, self.1)
+    }
+}
+
 #[inline]
 pub(crate) fn recv_msg(
     fd: BorrowedFd<'_>,
     buf: &mut MsgBuf<'_>,
     abuf: Option<&mut (sockaddr_storage, socklen_t)>,
 ) -> io::Result<RecvResult> {
-    struct Impl<'a>(BorrowedFd<'a>);
-    impl TruncatingRecvMsg for Impl<'_> {
-        type Error = io::Error;
-        type AddrBuf = (sockaddr_storage, socklen_t);
-        fn recv_trunc(
-            &mut self,
-            peek: bool,
-            buf: &mut MsgBuf<'_>,
-            abuf: Option<&mut (sockaddr_storage, socklen_t)>,
-        ) -> Result<Option<bool>, Self::Error> {
-            recv_trunc(self.0, peek, buf, abuf)
-        }
-    }
+    recv_msg_with_flags(fd, buf, abuf, 0)
+}
+
+/// Receives with a single call, without peeking, if `buf` is at least `max_msg_size` bytes large.
+pub(crate) fn recv_msg_with_max_size(
+    fd: BorrowedFd<'_>,
+    buf: &mut MsgBuf<'_>,
+    abuf: Option<&mut (sockaddr_storage, socklen_t)>,
+    max_msg_size: usize,
+) -> io::Result<RecvResult> {
     #[cfg(any(target_os = "linux", target_os = "android"))]
-    impl crate::TruncatingRecvMsgWithFullSize for Impl<'_> {
-        fn recv_trunc_with_full_size(
-            &mut self,
-            peek: bool,
-            buf: &mut MsgBuf<'_>,
-            abuf: Option<&mut (sockaddr_storage, socklen_t)>,
-        ) -> Result<TryRecvResult, Self::Error> {
-            recv_trunc_with_full_size(self.0, peek, buf, abuf)
-        }
+    {
+        crate::sync::recv_via_try_recv_with_max_size(&mut Impl(fd, 0), buf, abuf, max_msg_size)
     }
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    {
+        crate::sync::recv_via_recv_trunc_with_max_size(&mut Impl(fd, 0), buf, abuf, max_msg_size)
+    }
+}
 
+pub(crate) fn recv_msg_with_flags(
+    fd: BorrowedFd<'_>,
+    buf: &mut MsgBuf<'_>,
+    abuf: Option<&mut (sockaddr_storage, socklen_t)>,
+    flags: c_int,
+) -> io::Result<RecvResult> {
     #[cfg(any(target_os = "linux", target_os = "android"))]
     {
-        crate::sync::recv_via_try_recv(&mut Impl(fd), buf, abuf)
+        crate::sync::recv_via_try_recv(&mut Impl(fd, flags), buf, abuf)
     }
     #[cfg(not(any(target_os = "linux", target_os = "android")))]
     {
-        crate::sync::recv_via_recv_trunc(&mut Impl(fd), buf, abuf)
+        crate::sync::recv_via_recv_trunc(&mut Impl(fd, flags), buf, abuf)
     }
 }
+
+#[inline]
+pub(crate) fn recv_msg_vectored(
+    fd: BorrowedFd<'_>,
+    bufs: &mut [MsgBuf<'_>],
+    abuf: Option<&mut (sockaddr_storage, socklen_t)>,
+) -> io::Result<RecvResult> {
+    crate::sync::recv_vectored_via_recv_trunc_vectored(&mut Impl(fd, 0), bufs, abuf)
+}
+
+#[inline]
+pub(crate) fn recv_msg_with_cmsg(
+    fd: BorrowedFd<'_>,
+    buf: &mut MsgBuf<'_>,
+    cbuf: &mut CmsgBuf<'_>,
+    abuf: Option<&mut (sockaddr_storage, socklen_t)>,
+) -> io::Result<RecvResult> {
+    crate::os::unix::recv_with_cmsg_via_recv_trunc_with_cmsg(&mut Impl(fd, 0), buf, cbuf, abuf)
+}
@@ -1,5 +1,6 @@
-use super::{extract_address::*, r#impl::*};
-use crate::{MsgBuf, RecvResult};
+pub(crate) use super::extract_address::{extract_ip_address, extract_unix_address};
+use super::r#impl::*;
+use crate::{os::unix::CmsgBuf, MsgBuf, RecvResult};
 use libc::{sockaddr_storage, socklen_t};
 use std::{
     io,
@@ -8,7 +9,10 @@ use std::{
     os::{fd::BorrowedFd, unix::net::SocketAddr as UnixAddr},
 };
 
-fn prepare_storage() -> (sockaddr_storage, socklen_t) {
+/// The largest payload a UDP datagram can have, IPv6 jumbograms aside.
+const UDP_MAX_MSG_SIZE: usize = 65535;
+
+pub(super) fn prepare_storage() -> (sockaddr_storage, socklen_t) {
     (unsafe { zeroed() }, 0)
 }
 
@@ -54,6 +58,29 @@ pub(crate) fn recv_trunc_unix(
     Ok(ret)
 }
 
+pub(crate) fn recv_trunc_vectored_ip(
+    socket: BorrowedFd<'_>,
+    peek: bool,
+    bufs: &mut [MsgBuf<'_>],
+    abuf: Option<&mut InetAddr>,
+) -> io::Result<Option<bool>> {
+    let mut fused_abuf = prepare_storage();
+    let ret = recv_trunc_vectored(socket, peek, bufs, abuf.is_some().then_some(&mut fused_abuf))?;
+    extract_and_write_ip(abuf, &fused_abuf)?;
+    Ok(ret)
+}
+pub(crate) fn recv_trunc_vectored_unix(
+    socket: BorrowedFd<'_>,
+    peek: bool,
+    bufs: &mut [MsgBuf<'_>],
+    abuf: Option<&mut UnixAddr>,
+) -> io::Result<Option<bool>> {
+    let mut fused_abuf = prepare_storage();
+    let ret = recv_trunc_vectored(socket, peek, bufs, abuf.is_some().then_some(&mut fused_abuf))?;
+    extract_and_write_unix(abuf, &fused_abuf)?;
+    Ok(ret)
+}
+
 #[cfg(any(target_os = "linux", target_os = "android"))]
 pub(crate) fn recv_trunc_with_full_size_ip(
     socket: BorrowedFd<'_>,
@@ -88,7 +115,8 @@ pub(crate) fn recv_msg_ip(
     abuf: Option<&mut InetAddr>,
 ) -> io::Result<RecvResult> {
     let mut fused_abuf = prepare_storage();
-    let ret = recv_msg(socket, buf, abuf.is_some().then_some(&mut fused_abuf))?;
+    let fused = abuf.is_some().then_some(&mut fused_abuf);
+    let ret = recv_msg_with_max_size(socket, buf, fused, UDP_MAX_MSG_SIZE)?;
     extract_and_write_ip(abuf, &fused_abuf)?;
     Ok(ret)
 }
@@ -103,3 +131,87 @@ pub(crate) fn recv_msg_unix(
     extract_and_write_unix(abuf, &fused_abuf)?;
     Ok(ret)
 }
+
+pub(crate) fn recv_msg_vectored_ip(
+    socket: BorrowedFd<'_>,
+    bufs: &mut [MsgBuf<'_>],
+    abuf: Option<&mut InetAddr>,
+) -> io::Result<RecvResult> {
+    let mut fused_abuf = prepare_storage();
+    let ret = recv_msg_vectored(socket, bufs, abuf.is_some().then_some(&mut fused_abuf))?;
+    extract_and_write_ip(abuf, &fused_abuf)?;
+    Ok(ret)
+}
+
+pub(crate) fn recv_msg_vectored_unix(
+    socket: BorrowedFd<'_>,
+    bufs: &mut [MsgBuf<'_>],
+    abuf: Option<&mut UnixAddr>,
+) -> io::Result<RecvResult> {
+    let mut fused_abuf = prepare_storage();
+    let ret = recv_msg_vectored(socket, bufs, abuf.is_some().then_some(&mut fused_abuf))?;
+    extract_and_write_unix(abuf, &fused_abuf)?;
+    Ok(ret)
+}
+
+pub(crate) fn recv_trunc_with_cmsg_ip(
+    socket: BorrowedFd<'_>,
+    peek: bool,
+    buf: &mut MsgBuf<'_>,
+    cbuf: &mut CmsgBuf<'_>,
+    abuf: Option<&mut InetAddr>,
+) -> io::Result<Option<bool>> {
+    let mut fused_abuf = prepare_storage();
+    let ret = recv_trunc_with_cmsg(
+        socket,
+        peek,
+        buf,
+        cbuf,
+        abuf.is_some().then_some(&mut fused_abuf),
+        0,
+    )?;
+    extract_and_write_ip(abuf, &fused_abuf)?;
+    Ok(ret)
+}
+pub(crate) fn recv_trunc_with_cmsg_unix(
+    socket: BorrowedFd<'_>,
+    peek: bool,
+    buf: &mut MsgBuf<'_>,
+    cbuf: &mut CmsgBuf<'_>,
+    abuf: Option<&mut UnixAddr>,
+) -> io::Result<Option<bool>> {
+    let mut fused_abuf = prepare_storage();
+    let ret = recv_trunc_with_cmsg(
+        socket,
+        peek,
+        buf,
+        cbuf,
+        abuf.is_some().then_some(&mut fused_abuf),
+        0,
+    )?;
+    extract_and_write_unix(abuf, &fused_abuf)?;
+    Ok(ret)
+}
+
+pub(crate) fn recv_msg_with_cmsg_ip(
+    socket: BorrowedFd<'_>,
+    buf: &mut MsgBuf<'_>,
+    cbuf: &mut CmsgBuf<'_>,
+    abuf: Option<&mut InetAddr>,
+) -> io::Result<RecvResult> {
+    let mut fused_abuf = prepare_storage();
+    let ret = recv_msg_with_cmsg(socket, buf, cbuf, abuf.is_some().then_some(&mut fused_abuf))?;
+    extract_and_write_ip(abuf, &fused_abuf)?;
+    Ok(ret)
+}
+pub(crate) fn recv_msg_with_cmsg_unix(
+    socket: BorrowedFd<'_>,
+    buf: &mut MsgBuf<'_>,
+    cbuf: &mut CmsgBuf<'_>,
+    abuf: Option<&mut UnixAddr>,
+) -> io::Result<RecvResult> {
+    let mut fused_abuf = prepare_storage();
+    let ret = recv_msg_with_cmsg(socket, buf, cbuf, abuf.is_some().then_some(&mut fused_abuf))?;
+    extract_and_write_unix(abuf, &fused_abuf)?;
+    Ok(ret)
+}
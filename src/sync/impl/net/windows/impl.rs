@@ -47,6 +47,7 @@ pub(crate) fn recv_trunc(
         }
     });
     match ret {
+        // Zero bytes only ever means an empty datagram here, since this is only used with UDP.
         Ok(sz) => {
             let sz = sz as usize;
             unsafe {
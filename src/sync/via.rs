@@ -1,5 +1,5 @@
 use super::*;
-use crate::panic_try_recv_retcon;
+use crate::{GrowError, OversizePolicy, QuotaExceeded};
 
 /// Implements [`TruncatingRecvMsg::recv_trunc()`] via
 /// [`TruncatingRecvMsgWithFullSize::recv_trunc_with_full_size()`].
@@ -20,6 +20,10 @@ pub fn recv_trunc_via_recv_trunc_with_full_size<TRMWFS: TruncatingRecvMsgWithFul
 }
 
 /// Implements [`RecvMsg::recv_msg()`] via [`TruncatingRecvMsg::recv_trunc()`].
+///
+/// A message that doesn't fit into `buf` within its quota is dealt with according to the buffer's
+/// [oversize policy](OversizePolicy). The full size of the message is not known to this function,
+/// and thus isn't reported by `RecvResult::Truncated`.
 pub fn recv_via_recv_trunc<TRM: TruncatingRecvMsg + ?Sized>(
     slf: &mut TRM,
     buf: &mut MsgBuf<'_>,
@@ -41,16 +45,189 @@ pub fn recv_via_recv_trunc<TRM: TruncatingRecvMsg + ?Sized>(
         } else {
             fit_first = false;
             buf.set_fill(0);
-            if let Err(qe) = buf.clear_and_grow() {
-                return Ok(RecvResult::QuotaExceeded(qe));
+            match buf.try_clear_and_grow() {
+                Ok(()) => {}
+                Err(GrowError::QuotaExceeded(qe)) => {
+                    return recv_oversize(slf, buf, abuf, qe);
+                }
+                Err(e) => return Ok(e.into()),
             }
         }
     }
     slf.discard_msg()?;
-    Ok(if fit_first { RecvResult::Fit } else { RecvResult::Spilled })
+    Ok(buf.after_recv(if fit_first { RecvResult::Fit } else { RecvResult::Spilled }))
+}
+
+/// Applies the [oversize policy](OversizePolicy) of `buf` to the message at the front of the
+/// queue, which did not fit into `buf` within its quota.
+fn recv_oversize<TRM: TruncatingRecvMsg + ?Sized>(
+    slf: &mut TRM,
+    buf: &mut MsgBuf<'_>,
+    abuf: Option<&mut TRM::AddrBuf>,
+    qe: QuotaExceeded,
+) -> Result<RecvResult, TRM::Error> {
+    buf.set_fill(0);
+    buf.has_msg = false;
+    match buf.oversize {
+        OversizePolicy::LeaveQueued => {}
+        OversizePolicy::Discard => slf.discard_msg()?,
+        OversizePolicy::Truncate => {
+            if let Err(e) = buf.try_clear_and_grow_to_quota() {
+                return Ok(e.into());
+            }
+            return match slf.recv_trunc(false, buf, abuf) {
+                Ok(Some(true)) => Ok(buf.after_recv(RecvResult::Spilled)),
+                Ok(Some(false)) => Ok(buf.after_recv(RecvResult::Truncated { full_len: None })),
+                Ok(None) => Ok(RecvResult::EndOfStream),
+                Err(e) => {
+                    buf.set_fill(0);
+                    buf.has_msg = false;
+                    Err(e)
+                }
+            };
+        }
+    }
+    Ok(RecvResult::QuotaExceeded(qe))
+}
+
+/// Implements [`RecvMsgVectored::recv_msg_vectored()`] via
+/// [`TruncatingRecvMsgVectored::recv_trunc_vectored()`], growing the last buffer as necessary.
+///
+/// The [oversize policy](OversizePolicy) of the last buffer is used.
+///
+/// # Panics
+/// If `bufs` is empty.
+pub fn recv_vectored_via_recv_trunc_vectored<TRMV: TruncatingRecvMsgVectored + ?Sized>(
+    slf: &mut TRMV,
+    bufs: &mut [MsgBuf<'_>],
+    mut abuf: Option<&mut TRMV::AddrBuf>,
+) -> Result<RecvResult, TRMV::Error> {
+    assert!(!bufs.is_empty(), "no buffers to receive into");
+    let mut fit_first = true;
+    loop {
+        let fit = match slf.recv_trunc_vectored(true, bufs, abuf.as_deref_mut()) {
+            Ok(Some(fit)) => fit,
+            Ok(None) => return Ok(RecvResult::EndOfStream),
+            Err(e) => {
+                clear_all(bufs);
+                return Err(e);
+            }
+        };
+        if fit {
+            break;
+        } else {
+            fit_first = false;
+            clear_all(bufs);
+            let last = bufs.last_mut().unwrap();
+            match last.try_clear_and_grow() {
+                Ok(()) => {}
+                Err(GrowError::QuotaExceeded(qe)) => {
+                    return recv_vectored_oversize(slf, bufs, abuf, qe);
+                }
+                Err(e) => return Ok(e.into()),
+            }
+        }
+    }
+    slf.discard_msg()?;
+    let rslt = if fit_first { RecvResult::Fit } else { RecvResult::Spilled };
+    Ok(bufs.last_mut().unwrap().after_recv(rslt))
+}
+
+/// Like [`recv_oversize()`], but for vectored reception, in which the last buffer grows.
+fn recv_vectored_oversize<TRMV: TruncatingRecvMsgVectored + ?Sized>(
+    slf: &mut TRMV,
+    bufs: &mut [MsgBuf<'_>],
+    abuf: Option<&mut TRMV::AddrBuf>,
+    qe: QuotaExceeded,
+) -> Result<RecvResult, TRMV::Error> {
+    clear_all(bufs);
+    let last = bufs.last_mut().unwrap();
+    match last.oversize {
+        OversizePolicy::LeaveQueued => {}
+        OversizePolicy::Discard => slf.discard_msg()?,
+        OversizePolicy::Truncate => {
+            if let Err(e) = last.try_clear_and_grow_to_quota() {
+                return Ok(e.into());
+            }
+            return match slf.recv_trunc_vectored(false, bufs, abuf) {
+                Ok(Some(fit)) => {
+                    let rslt = if fit {
+                        RecvResult::Spilled
+                    } else {
+                        RecvResult::Truncated { full_len: None }
+                    };
+                    Ok(bufs.last_mut().unwrap().after_recv(rslt))
+                }
+                Ok(None) => Ok(RecvResult::EndOfStream),
+                Err(e) => {
+                    clear_all(bufs);
+                    Err(e)
+                }
+            };
+        }
+    }
+    Ok(RecvResult::QuotaExceeded(qe))
+}
+
+pub(crate) fn clear_all(bufs: &mut [MsgBuf<'_>]) {
+    for buf in bufs {
+        buf.set_fill(0);
+        buf.has_msg = false;
+    }
+}
+
+/// Like [`recv_via_recv_trunc()`], but skips peeking if the capacity of `buf` is at least
+/// `max_msg_size`, receiving the message directly instead.
+///
+/// `max_msg_size` must be the largest size a message received from `slf` can have (such as 65535
+/// for UDP). Buffers that are smaller than that are handled by the peek-and-grow loop of
+/// [`recv_via_recv_trunc()`].
+///
+/// Should a message received without peeking get truncated nonetheless – which means that
+/// `max_msg_size` was not actually the maximum message size, or that a peer is misbehaving – the
+/// truncated message is returned with `RecvResult::Truncated`, regardless of the
+/// [oversize policy](OversizePolicy), since the rest of it is already gone by then.
+pub fn recv_via_recv_trunc_with_max_size<TRM: TruncatingRecvMsg + ?Sized>(
+    slf: &mut TRM,
+    buf: &mut MsgBuf<'_>,
+    abuf: Option<&mut TRM::AddrBuf>,
+    max_msg_size: usize,
+) -> Result<RecvResult, TRM::Error> {
+    if buf.capacity() < max_msg_size {
+        return recv_via_recv_trunc(slf, buf, abuf);
+    }
+    match slf.recv_trunc(false, buf, abuf) {
+        Ok(Some(true)) => Ok(buf.after_recv(RecvResult::Fit)),
+        Ok(Some(false)) => Ok(buf.after_recv(RecvResult::Truncated { full_len: None })),
+        Ok(None) => Ok(RecvResult::EndOfStream),
+        Err(e) => {
+            buf.set_fill(0);
+            buf.has_msg = false;
+            Err(e)
+        }
+    }
 }
 
 /// Implements [`RecvMsg::recv_msg()`] via [`TruncatingRecvMsgWithFullSizeExt::try_recv_msg()`].
+///
+/// A message that fits into `buf` on the first try is received with a peek and a discard, which
+/// takes **two** receive calls. If it doesn't fit, the buffer is grown to the size reported by the
+/// peek and the message is then taken off the queue directly, without peeking it a second time.
+///
+/// Receiving a message that fits with a single call is impossible with this trait alone: a
+/// non-peeking receive into a buffer that turns out to be too small takes the whole message off
+/// the queue, leaving nothing to retry with, and nothing short of receiving tells how large the
+/// message is. Where the largest possible message size is known, use
+/// [`recv_via_try_recv_with_max_size()`] instead, which takes a single call whenever the buffer is
+/// at least that large. (`RecvMsg` for `UdpSocket` does this on its own.)
+///
+/// Because the message is not peeked a second time, another receiver taking it off a shared socket
+/// in between the two calls makes this one receive the next message in its place. Should that one
+/// not fit into the grown buffer, it is received truncated, and the result is
+/// `RecvResult::Truncated` with its full size.
+///
+/// The [oversize policy](OversizePolicy) of `buf` is honored, with `RecvResult::Truncated`
+/// reporting the full size of the message.
 pub fn recv_via_try_recv<TRMWFS: TruncatingRecvMsgWithFullSize + ?Sized>(
     slf: &mut TRMWFS,
     buf: &mut MsgBuf<'_>,
@@ -58,16 +235,75 @@ pub fn recv_via_try_recv<TRMWFS: TruncatingRecvMsgWithFullSize + ?Sized>(
 ) -> Result<RecvResult, TRMWFS::Error> {
     let ok = match slf.try_recv_msg(buf, abuf.as_deref_mut())? {
         TryRecvResult::Spilled(sz) => {
-            if let Err(qe) = buf.clear_and_grow_to(sz) {
-                return Ok(RecvResult::QuotaExceeded(qe));
+            match buf.try_clear_and_grow_to(sz) {
+                Ok(()) => {}
+                Err(GrowError::QuotaExceeded(qe)) => {
+                    return try_recv_oversize(slf, buf, abuf, qe);
+                }
+                Err(e) => return Ok(e.into()),
             }
-            match slf.try_recv_msg(buf, abuf)? {
+            match slf.recv_trunc_with_full_size(false, buf, abuf)? {
                 TryRecvResult::Fit => RecvResult::Spilled,
-                TryRecvResult::Spilled(..) => panic_try_recv_retcon(),
+                // Another receiver took the peeked message, and the next one is larger
+                TryRecvResult::Spilled(full_len) => {
+                    RecvResult::Truncated { full_len: Some(full_len) }
+                }
                 TryRecvResult::EndOfStream => return Ok(RecvResult::EndOfStream),
             }
         }
         fit_or_end => fit_or_end.into(),
     };
-    Ok(ok)
+    Ok(buf.after_recv(ok))
+}
+
+/// Like [`recv_oversize()`], but reports the full size of truncated messages.
+fn try_recv_oversize<TRMWFS: TruncatingRecvMsgWithFullSize + ?Sized>(
+    slf: &mut TRMWFS,
+    buf: &mut MsgBuf<'_>,
+    abuf: Option<&mut TRMWFS::AddrBuf>,
+    qe: QuotaExceeded,
+) -> Result<RecvResult, TRMWFS::Error> {
+    if buf.oversize != OversizePolicy::Truncate {
+        return recv_oversize(slf, buf, abuf, qe);
+    }
+    buf.has_msg = false;
+    if let Err(e) = buf.try_clear_and_grow_to_quota() {
+        return Ok(e.into());
+    }
+    let rslt = match slf.recv_trunc_with_full_size(false, buf, abuf)? {
+        TryRecvResult::Fit => RecvResult::Spilled,
+        TryRecvResult::Spilled(full_len) => RecvResult::Truncated { full_len: Some(full_len) },
+        TryRecvResult::EndOfStream => RecvResult::EndOfStream,
+    };
+    Ok(buf.after_recv(rslt))
+}
+
+/// Like [`recv_via_try_recv()`], but receives the message with a single non-peeking call if the
+/// capacity of `buf` is at least `max_msg_size`. Smaller buffers go through the strategy of
+/// [`recv_via_try_recv()`].
+///
+/// `max_msg_size` must be the largest size a message received from `slf` can have (such as 65535
+/// for UDP). Should a message received without peeking get truncated nonetheless, it is returned
+/// with `RecvResult::Truncated` and its full size, regardless of the
+/// [oversize policy](OversizePolicy), since the rest of it is already gone by then.
+pub fn recv_via_try_recv_with_max_size<TRMWFS: TruncatingRecvMsgWithFullSize + ?Sized>(
+    slf: &mut TRMWFS,
+    buf: &mut MsgBuf<'_>,
+    abuf: Option<&mut TRMWFS::AddrBuf>,
+    max_msg_size: usize,
+) -> Result<RecvResult, TRMWFS::Error> {
+    if buf.capacity() < max_msg_size {
+        return recv_via_try_recv(slf, buf, abuf);
+    }
+    let rslt = match slf.recv_trunc_with_full_size(false, buf, abuf) {
+        Ok(TryRecvResult::Fit) => RecvResult::Fit,
+        Ok(TryRecvResult::Spilled(full_len)) => RecvResult::Truncated { full_len: Some(full_len) },
+        Ok(TryRecvResult::EndOfStream) => return Ok(RecvResult::EndOfStream),
+        Err(e) => {
+            buf.set_fill(0);
+            buf.has_msg = false;
+            return Err(e);
+        }
+    };
+    Ok(buf.after_recv(rslt))
 }